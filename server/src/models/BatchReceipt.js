@@ -44,6 +44,9 @@ const batchReceiptSchema = new mongoose.Schema({
   },
   totalUsd: Number,
 
+  // Conversion rate the batch was paid at
+  lamportsPer1000Points: Number,
+
   // Breakdown per NGO
   allocations: [allocationSchema],
 
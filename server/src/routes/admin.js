@@ -210,14 +210,15 @@ router.post('/vault/deposit', checkAdminKey, async (req, res) => {
     // Get the deposit method from the program
     const amountLamports = Math.floor(amount * 1_000_000_000);
 
-    const { configPda, sponsorRegistryPda, escrowVaultPda } = solanaClient.getPdas();
+    const { configPda, escrowVaultPda } = solanaClient.getPdas();
 
     const tx = await solanaClient.program.methods
-      .deposit(new BN(amountLamports))
+      .deposit(new BN(amountLamports), null)
       .accountsPartial({
         sponsor: solanaClient.wallet.publicKey,
         config: configPda,
-        sponsorRegistry: sponsorRegistryPda,
+        // Dev deposits are not tracked in a sponsor ledger
+        sponsorAccount: null,
         escrowVault: escrowVaultPda,
       })
       .signers([solanaClient.wallet])
@@ -8,9 +8,10 @@ import Ngo from '../models/Ngo.js';
 import BatchReceipt from '../models/BatchReceipt.js';
 import User from '../models/User.js';
 
-// Conversion constants (must match Solana program)
+// Conversion constants. The live points-to-lamports rate is read from the
+// program's Config; the default is only used in demo mode and for previews.
 const LAMPORTS_PER_SOL = 1_000_000_000;
-const LAMPORTS_PER_1000_POINTS = 100_000_000; // 0.1 SOL per 1000 points
+const DEFAULT_LAMPORTS_PER_1000_POINTS = 100_000_000; // 0.1 SOL per 1000 points
 const POINTS_PER_DOLLAR = 100;
 
 /**
@@ -28,16 +29,17 @@ export function getCurrentWeekNumber() {
 /**
  * Convert points to lamports
  */
-export function pointsToLamports(points) {
-  return Math.floor((points * LAMPORTS_PER_1000_POINTS) / 1000);
+export function pointsToLamports(points, lamportsPer1000Points = DEFAULT_LAMPORTS_PER_1000_POINTS) {
+  return Math.floor((points * lamportsPer1000Points) / 1000);
 }
 
 /**
- * Aggregate pending pledges for a week
+ * Aggregate pending pledges for a week, including pledges held back from
+ * earlier weeks for being under the on-chain minimum
  */
-export async function aggregatePledges(weekNumber) {
+export async function aggregatePledges(weekNumber, lamportsPer1000Points = DEFAULT_LAMPORTS_PER_1000_POINTS) {
   const pledges = await Pledge.aggregate([
-    { $match: { weekNumber, status: 'pending' } },
+    { $match: { weekNumber: { $lte: weekNumber }, status: 'pending' } },
     {
       $group: {
         _id: '$ngoId',
@@ -62,7 +64,7 @@ export async function aggregatePledges(weekNumber) {
         ngoName: ngo.name,
         ngoWallet: ngo.walletAddress,
         totalPoints: p.totalPoints,
-        lamports: pointsToLamports(p.totalPoints),
+        lamports: pointsToLamports(p.totalPoints, lamportsPer1000Points),
         pledgeIds: p.pledgeIds
       };
     });
@@ -87,8 +89,23 @@ export async function processWeeklyBatch(weekNumber = null, solanaClient = null)
   // In dev, use unique ID to allow multiple test runs
   const effectiveWeek = process.env.NODE_ENV === 'production' ? week : Date.now();
 
-  // Aggregate pledges
-  const allocations = await aggregatePledges(week);
+  // Use the on-chain conversion rate (or feed price) so estimates match
+  // what the program pays
+  const lamportsPer1000Points = solanaClient
+    ? await solanaClient.getConversionRate()
+    : DEFAULT_LAMPORTS_PER_1000_POINTS;
+
+  // Aggregate pledges. NGOs under the on-chain minimum are held back; their
+  // pledges stay pending and roll into a later week's batch.
+  let allocations = await aggregatePledges(week, lamportsPer1000Points);
+  if (solanaClient) {
+    const { minPoints } = await solanaClient.getPledgeLimits();
+    const heldBack = allocations.filter(a => a.totalPoints < minPoints);
+    heldBack.forEach(a => {
+      console.log(`  Rolling over ${a.ngoName}: ${a.totalPoints} points is under the ${minPoints} point minimum`);
+    });
+    allocations = allocations.filter(a => a.totalPoints >= minPoints);
+  }
 
   if (allocations.length === 0) {
     console.log('No pending pledges for this week');
@@ -144,6 +161,7 @@ export async function processWeeklyBatch(weekNumber = null, solanaClient = null)
     totalPointsRedeemed: totalPoints,
     totalLamports,
     totalUsd,
+    lamportsPer1000Points,
     cluster,
     allocations: allocations.map(a => ({
       ngoId: a.ngoId,
@@ -17,9 +17,11 @@ const PROGRAM_ID = new PublicKey('Ff9wbBku1gd8wEoXej6YMxqiyw6eUEGqzCJBNLoHzTqv')
 
 // PDA seeds (must match the Rust program - v3)
 const CONFIG_SEED = 'config_v3';
-const NGO_REGISTRY_SEED = 'ngo_registry_v3';
-const SPONSOR_REGISTRY_SEED = 'sponsor_registry_v3';
+const NGO_SEED = 'ngo_v3';
+const SPONSOR_SEED = 'sponsor_v3';
 const ESCROW_SEED = 'escrow_v3';
+const PROPOSAL_SEED = 'proposal_v3';
+const ROLE_SEED = 'role_v3';
 
 class SolanaClient {
   constructor(cluster = 'devnet') {
@@ -107,20 +109,39 @@ class SolanaClient {
       [Buffer.from(CONFIG_SEED)],
       PROGRAM_ID
     );
-    const [ngoRegistryPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from(NGO_REGISTRY_SEED)],
+    const [escrowVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(ESCROW_SEED)],
       PROGRAM_ID
     );
-    const [sponsorRegistryPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from(SPONSOR_REGISTRY_SEED)],
+
+    return { configPda, escrowVaultPda };
+  }
+
+  // Derive the NGO account PDA for an NGO wallet
+  getNgoPda(wallet) {
+    const [ngoPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(NGO_SEED), wallet.toBuffer()],
       PROGRAM_ID
     );
-    const [escrowVaultPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from(ESCROW_SEED)],
+    return ngoPda;
+  }
+
+  // Derive a proposal PDA from its id
+  getProposalPda(proposalId) {
+    const [proposalPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(PROPOSAL_SEED), new BN(proposalId).toArrayLike(Buffer, 'le', 8)],
       PROGRAM_ID
     );
+    return proposalPda;
+  }
 
-    return { configPda, ngoRegistryPda, sponsorRegistryPda, escrowVaultPda };
+  // Derive the role assignment PDA for a key
+  getRolePda(holder) {
+    const [rolePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(ROLE_SEED), holder.toBuffer()],
+      PROGRAM_ID
+    );
+    return rolePda;
   }
 
   /**
@@ -143,9 +164,15 @@ class SolanaClient {
     try {
       const config = await this.program.account.config.fetch(configPda);
       return {
-        admin: config.admin.toString(),
+        admins: config.admins.map(a => a.toString()),
+        threshold: config.threshold,
         totalDeposited: config.totalDeposited.toNumber() / LAMPORTS_PER_SOL,
         totalDisbursed: config.totalDisbursed.toNumber() / LAMPORTS_PER_SOL,
+        totalWithdrawn: config.totalWithdrawn.toNumber() / LAMPORTS_PER_SOL,
+        lamportsPer1000Points: config.lamportsPer1000Points.toNumber(),
+        priceFeed: config.priceFeed ? config.priceFeed.toString() : null,
+        minPledgePoints: config.minPledgePoints.toNumber(),
+        maxPledgePoints: config.maxPledgePoints.toNumber(),
         totalPointsRedeemed: config.totalPointsRedeemed.toNumber(),
         lastBatchWeek: config.lastBatchWeek
       };
@@ -156,41 +183,89 @@ class SolanaClient {
   }
 
   /**
-   * Get registered NGOs from on-chain registry
+   * Get the points-to-lamports conversion rate batches currently pay at:
+   * derived from the SOL/USD price feed when Config names one, otherwise
+   * the fixed rate. With a feed the program re-reads the price at execution.
+   */
+  async getConversionRate() {
+    const { configPda } = this.getPdas();
+    const config = await this.program.account.config.fetch(configPda);
+    if (!config.priceFeed) {
+      return config.lamportsPer1000Points.toNumber();
+    }
+
+    // Pyth price account layout: exponent at 20, aggregate price at 208
+    const feed = await this.connection.getAccountInfo(config.priceFeed);
+    const expo = feed.data.readInt32LE(20);
+    const price = feed.data.readBigInt64LE(208);
+    // 1000 points = $10 (100 points = $1)
+    const usdLamports = 10n * BigInt(LAMPORTS_PER_SOL);
+    const rate = expo <= 0
+      ? (usdLamports * 10n ** BigInt(-expo)) / price
+      : usdLamports / (price * 10n ** BigInt(expo));
+    return Number(rate);
+  }
+
+  /**
+   * Get the points one NGO may be allocated in a batch (maxPoints 0 = no maximum)
+   */
+  async getPledgeLimits() {
+    const { configPda } = this.getPdas();
+    const config = await this.program.account.config.fetch(configPda);
+    return {
+      minPoints: config.minPledgePoints.toNumber(),
+      maxPoints: config.maxPledgePoints.toNumber(),
+      rollOver: config.rollOverSmallPledges
+    };
+  }
+
+  /**
+   * Get whitelisted NGOs from their on-chain accounts
    */
   async getNgos() {
-    const { ngoRegistryPda } = this.getPdas();
     try {
-      const registry = await this.program.account.ngoRegistry.fetch(ngoRegistryPda);
-      return registry.ngos.map(ngo => ({
-        pubkey: ngo.pubkey.toString(),
+      const accounts = await this.program.account.ngoAccount.all();
+      return accounts.map(({ account: ngo }) => ({
+        pubkey: ngo.wallet.toString(),
         name: ngo.name,
+        website: ngo.website,
+        metadataUri: ngo.metadataUri,
+        contentHash: Buffer.from(ngo.contentHash).toString('hex'),
+        category: Object.keys(ngo.category)[0],
+        countryCode: ngo.countryCode.some(b => b !== 0)
+          ? Buffer.from(ngo.countryCode).toString()
+          : null,
         totalReceived: ngo.totalReceived.toNumber() / LAMPORTS_PER_SOL,
-        disbursementCount: ngo.disbursementCount,
+        disbursementCount: ngo.disbursementCount.toNumber(),
+        lastDisbursement: ngo.lastDisbursement.toNumber(),
+        verifiedUntil: ngo.verifiedUntil.toNumber(),
         isActive: ngo.isActive
       }));
     } catch (error) {
-      console.error('Failed to fetch NGO registry:', error.message);
+      console.error('Failed to fetch NGO accounts:', error.message);
       return [];
     }
   }
 
   /**
-   * Get registered sponsors from on-chain registry
+   * Get registered sponsors from their on-chain accounts
+   * (anonymous depositor ledgers are skipped)
    */
   async getSponsors() {
-    const { sponsorRegistryPda } = this.getPdas();
     try {
-      const registry = await this.program.account.sponsorRegistry.fetch(sponsorRegistryPda);
-      return registry.sponsors.map(sponsor => ({
-        pubkey: sponsor.pubkey.toString(),
-        name: sponsor.name,
-        totalDeposited: sponsor.totalDeposited.toNumber() / LAMPORTS_PER_SOL,
-        depositCount: sponsor.depositCount,
-        isVerified: sponsor.isVerified
-      }));
+      const accounts = await this.program.account.sponsorAccount.all();
+      return accounts
+        .filter(({ account }) => account.isRegistered)
+        .map(({ account: sponsor }) => ({
+          pubkey: sponsor.wallet.toString(),
+          name: sponsor.name,
+          totalDeposited: sponsor.totalDeposited.toNumber() / LAMPORTS_PER_SOL,
+          totalWithdrawn: sponsor.totalWithdrawn.toNumber() / LAMPORTS_PER_SOL,
+          depositCount: sponsor.depositCount,
+          isVerified: sponsor.isVerified
+        }));
     } catch (error) {
-      console.error('Failed to fetch sponsor registry:', error.message);
+      console.error('Failed to fetch sponsor accounts:', error.message);
       return [];
     }
   }
@@ -201,7 +276,7 @@ class SolanaClient {
    * @param {Array} allocations - Array of { ngoWallet, totalPoints }
    */
   async batchDisburse(weekId, allocations) {
-    const { configPda, ngoRegistryPda, escrowVaultPda } = this.getPdas();
+    const { configPda, escrowVaultPda } = this.getPdas();
 
     // Format allocations for the program
     const formattedAllocations = allocations.map(a => ({
@@ -209,12 +284,32 @@ class SolanaClient {
       pointsPledged: new BN(a.totalPoints)
     }));
 
-    // Build remaining accounts (NGO wallets to receive funds)
-    const remainingAccounts = allocations.map(a => ({
-      pubkey: new PublicKey(a.ngoWallet),
-      isSigner: false,
-      isWritable: true
-    }));
+    // Build remaining accounts (each NGO wallet followed by its NGO account PDA)
+    const remainingAccounts = allocations.flatMap(a => {
+      const wallet = new PublicKey(a.ngoWallet);
+      return [
+        { pubkey: wallet, isSigner: false, isWritable: true },
+        { pubkey: this.getNgoPda(wallet), isSigner: false, isWritable: true }
+      ];
+    });
+
+    // Open earmarks follow the NGO pairs so matching NGOs draw from them first
+    const earmarks = await this.program.account.earmark.all();
+    for (const { publicKey, account } of earmarks) {
+      if (account.remaining.gtn(0)) {
+        remainingAccounts.push({ pubkey: publicKey, isSigner: false, isWritable: true });
+      }
+    }
+
+    // Running matching campaigns with funds left add their match on top
+    const now = Math.floor(Date.now() / 1000);
+    const campaigns = await this.program.account.matchingCampaign.all();
+    for (const { publicKey, account } of campaigns) {
+      const running = account.startsAt.toNumber() <= now && now < account.endsAt.toNumber();
+      if (running && account.cap.gt(account.matched)) {
+        remainingAccounts.push({ pubkey: publicKey, isSigner: false, isWritable: true });
+      }
+    }
 
     try {
       console.log('Batch disburse params:', {
@@ -225,17 +320,40 @@ class SolanaClient {
         })),
         admin: this.wallet.publicKey.toString(),
         config: configPda.toString(),
-        ngoRegistry: ngoRegistryPda.toString(),
         escrowVault: escrowVaultPda.toString(),
       });
 
+      // Privileged actions go through a multisig proposal. This wallet's
+      // approval is recorded on creation, which is enough when the
+      // threshold is 1; otherwise execution fails until others approve.
+      const config = await this.program.account.config.fetch(configPda);
+      const proposalPda = this.getProposalPda(config.proposalCount);
+
+      const proposalTx = await this.program.methods
+        .createProposal({
+          batchDisburse: { weekId: new BN(weekId), allocations: formattedAllocations }
+        })
+        .accountsPartial({
+          proposer: this.wallet.publicKey,
+          config: configPda,
+          proposal: proposalPda,
+        })
+        .signers([this.wallet])
+        .rpc();
+
+      console.log(`Batch proposal ${proposalPda.toString()} TX: ${proposalTx}`);
+
       const tx = await this.program.methods
         .batchDisburse(new BN(weekId), formattedAllocations)
         .accountsPartial({
-          admin: this.wallet.publicKey,
+          // This wallet must hold the Disburser role
+          operator: this.wallet.publicKey,
+          operatorRoles: this.getRolePda(this.wallet.publicKey),
           config: configPda,
-          ngoRegistry: ngoRegistryPda,
+          proposal: proposalPda,
           escrowVault: escrowVaultPda,
+          // Required when Config prices batches from a SOL/USD feed
+          priceFeed: config.priceFeed,
         })
         .remainingAccounts(remainingAccounts)
         .signers([this.wallet])
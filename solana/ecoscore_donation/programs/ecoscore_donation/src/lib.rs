@@ -1,6 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as MintState;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 declare_id!("Ff9wbBku1gd8wEoXej6YMxqiyw6eUEGqzCJBNLoHzTqv");
 
@@ -8,27 +14,97 @@ declare_id!("Ff9wbBku1gd8wEoXej6YMxqiyw6eUEGqzCJBNLoHzTqv");
 // CONSTANTS
 // =============================================================================
 
-/// Maximum number of NGOs that can be whitelisted
+/// Maximum number of NGOs the legacy `NgoRegistry` account could hold
 const MAX_NGOS: usize = 50;
 
-/// Maximum number of sponsors that can be registered
+/// Maximum number of sponsors the legacy `SponsorRegistry` account could hold
 const MAX_SPONSORS: usize = 50;
 
+/// Maximum length of an NGO website URL
+const MAX_WEBSITE_LEN: usize = 128;
+
+/// Maximum length of an NGO metadata URI (e.g. `ipfs://<CID>`)
+const MAX_METADATA_URI_LEN: usize = 128;
+
 /// Maximum NGOs per batch (to fit in transaction size limits)
 const MAX_BATCH_SIZE: usize = 10;
 
-/// Minimum points required for a pledge (500 points = $5)
+/// Maximum length of the campaign tag on a deposit receipt
+const MAX_CAMPAIGN_TAG_LEN: usize = 32;
+
+/// Maximum number of NGOs a matching campaign can name
+const MAX_CAMPAIGN_NGOS: usize = 10;
+
+/// Maximum number of admin signers in the multisig
+const MAX_ADMINS: usize = 10;
+
+/// Upper bound on the timelock delay (30 days)
+const MAX_TIMELOCK_DELAY: i64 = 30 * 24 * 60 * 60;
+
+/// Default notice before a sponsor withdrawal can execute (7 days, so the
+/// weekly batch runs first)
+const DEFAULT_WITHDRAWAL_NOTICE: i64 = 7 * 24 * 60 * 60;
+
+/// Upper bound on the withdrawal notice period (30 days)
+const MAX_WITHDRAWAL_NOTICE: i64 = 30 * 24 * 60 * 60;
+
+/// How long a new NGO's verification lasts before it must be renewed (1 year)
+const VERIFICATION_PERIOD: i64 = 365 * 24 * 60 * 60;
+
+/// Furthest ahead a renewal may set `verified_until` (2 years)
+const MAX_VERIFICATION_PERIOD: i64 = 2 * VERIFICATION_PERIOD;
+
+/// Number of `NgoCategory` variants (length of `Config.category_caps_bps`)
+const NGO_CATEGORY_COUNT: usize = 12;
+const _: () = assert!(NgoCategory::Other as usize + 1 == NGO_CATEGORY_COUNT);
+
+/// Pause flags for the emergency circuit breaker (bitmask in `Config.paused`)
+const PAUSE_DEPOSITS: u8 = 1 << 0;
+const PAUSE_DISBURSEMENTS: u8 = 1 << 1;
+const PAUSE_REGISTRY: u8 = 1 << 2;
+const PAUSE_ALL: u8 = PAUSE_DEPOSITS | PAUSE_DISBURSEMENTS | PAUSE_REGISTRY;
+
+/// Initial minimum points per NGO in a batch (500 points = $5). The live
+/// limit is `Config.min_pledge_points`, changed with `SetPledgeLimits`.
 const MIN_PLEDGE_POINTS: u64 = 500;
 
-/// Conversion rate: 100 points = $1 ≈ 0.01 SOL (at $100/SOL)
-/// 1000 points = 0.1 SOL = 100_000_000 lamports
-const LAMPORTS_PER_1000_POINTS: u64 = 100_000_000;
+/// Initial conversion rate: 100 points = $1 ≈ 0.01 SOL (at $100/SOL)
+/// 1000 points = 0.1 SOL = 100_000_000 lamports. The live rate is
+/// `Config.lamports_per_1000_points`, changed with `SetConversionRate`.
+const DEFAULT_LAMPORTS_PER_1000_POINTS: u64 = 100_000_000;
+
+/// Points per US dollar when batches are priced from a SOL/USD feed
+const POINTS_PER_USD: u64 = 100;
+
+/// Longest `Config.max_price_age` governance can set (1 hour)
+const MAX_PRICE_AGE: i64 = 60 * 60;
+
+/// Pyth price account layout (v2): only the header and aggregate price
+/// are read, so a feed must be at least `PYTH_PRICE_ACCOUNT_LEN` bytes
+const PYTH_MAGIC: u32 = 0xa1b2_c3d4;
+const PYTH_VERSION: u32 = 2;
+const PYTH_PRICE_ACCOUNT_TYPE: u32 = 3;
+const PYTH_STATUS_TRADING: u32 = 1;
+const PYTH_PRICE_ACCOUNT_LEN: usize = 240;
 
 /// Seeds for PDA derivation (v3 for fresh devnet deployment with full registry)
 const ESCROW_SEED: &[u8] = b"escrow_v3";
 const CONFIG_SEED: &[u8] = b"config_v3";
 const NGO_REGISTRY_SEED: &[u8] = b"ngo_registry_v3";
+const NGO_SEED: &[u8] = b"ngo_v3";
+const SPONSOR_SEED: &[u8] = b"sponsor_v3";
+const NGO_APPLICATION_SEED: &[u8] = b"ngo_application_v3";
 const SPONSOR_REGISTRY_SEED: &[u8] = b"sponsor_registry_v3";
+const PROPOSAL_SEED: &[u8] = b"proposal_v3";
+const ADMIN_HISTORY_SEED: &[u8] = b"admin_history_v3";
+const ROLE_SEED: &[u8] = b"role_v3";
+const MINT_CONFIG_SEED: &[u8] = b"mint_config_v3";
+const EARMARK_SEED: &[u8] = b"earmark_v3";
+const MATCHING_CAMPAIGN_SEED: &[u8] = b"matching_campaign_v3";
+const DEPOSIT_RECEIPT_SEED: &[u8] = b"deposit_receipt_v3";
+const WITHDRAWAL_SEED: &[u8] = b"withdrawal_v3";
+const MOCK_PRICE_FEED_SEED: &[u8] = b"mock_price_feed_v3";
+const RETIRED_NGO_WALLET_SEED: &[u8] = b"retired_ngo_wallet_v3";
 
 // =============================================================================
 // PROGRAM INSTRUCTIONS
@@ -41,30 +117,56 @@ pub mod ecoscore_donation {
     /// Initialize the escrow system
     ///
     /// This creates:
-    /// - A Config account to store admin and totals
-    /// - An NGO Registry to track whitelisted recipients
-    /// - A Sponsor Registry to track verified brand partners
+    /// - A Config account to store the admin set and totals
     /// - An Escrow Vault PDA to hold funds
     ///
+    /// The initializer becomes the sole admin with a threshold of 1.
+    /// Additional admins are added through a `SetAdmins` proposal.
+    /// `timelock_delay` must be non-zero so a fresh deployment never runs
+    /// timelocked actions instantly; only a `SetTimelock` proposal, itself
+    /// delayed, can remove it.
+    ///
     /// Only needs to be called once when setting up the system.
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+    pub fn initialize(ctx: Context<Initialize>, timelock_delay: i64) -> Result<()> {
+        require!(
+            (1..=MAX_TIMELOCK_DELAY).contains(&timelock_delay),
+            EscrowError::InvalidTimelockDelay
+        );
+
         let config = &mut ctx.accounts.config;
-        config.admin = ctx.accounts.admin.key();
+        config.admins = vec![ctx.accounts.admin.key()];
+        config.threshold = 1;
+        config.proposal_count = 0;
+        config.pending_admin = None;
+        config.admin_change_count = 0;
+        config.timelock_delay = timelock_delay;
+        config.timelock_disburse_threshold = 0;
+        config.paused = 0;
+        config.category_caps_bps = [10000; NGO_CATEGORY_COUNT];
         config.total_deposited = 0;
         config.total_disbursed = 0;
         config.total_points_redeemed = 0;
         config.last_batch_week = 0;
+        config.earmark_count = 0;
+        config.earmarked_balance = 0;
+        config.campaign_count = 0;
+        config.matching_balance = 0;
+        config.ngo_count = 0;
+        config.withdrawal_notice = DEFAULT_WITHDRAWAL_NOTICE;
+        config.sponsor_shares = 0;
+        config.attributed_balance = 0;
+        config.total_withdrawn = 0;
+        config.lamports_per_1000_points = DEFAULT_LAMPORTS_PER_1000_POINTS;
+        config.price_feed = None;
+        config.max_price_age = 0;
+        config.max_price_confidence_bps = 0;
+        config.min_pledge_points = MIN_PLEDGE_POINTS;
+        config.max_pledge_points = 0;
+        config.roll_over_small_pledges = false;
+        config.vault_buffer = 0;
         config.bump = ctx.bumps.config;
         config.vault_bump = ctx.bumps.escrow_vault;
 
-        let ngo_registry = &mut ctx.accounts.ngo_registry;
-        ngo_registry.ngos = Vec::new();
-        ngo_registry.bump = ctx.bumps.ngo_registry;
-
-        let sponsor_registry = &mut ctx.accounts.sponsor_registry;
-        sponsor_registry.sponsors = Vec::new();
-        sponsor_registry.bump = ctx.bumps.sponsor_registry;
-
         emit!(InitializeEvent {
             admin: ctx.accounts.admin.key(),
             timestamp: Clock::get()?.unix_timestamp,
@@ -74,639 +176,5888 @@ pub mod ecoscore_donation {
         Ok(())
     }
 
-    /// Deposit SOL into the escrow vault
+    /// Upgrade a deployment's config from the legacy single-admin layout
     ///
-    /// Any sponsor (brand partner) can deposit funds.
-    /// Funds are held in the vault PDA until disbursed to NGOs.
-    /// If the sponsor is registered, their totals are updated.
-    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
-        require!(amount > 0, EscrowError::InvalidAmount);
-
-        // Transfer SOL from sponsor to escrow vault
-        let cpi_context = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.sponsor.to_account_info(),
-                to: ctx.accounts.escrow_vault.to_account_info(),
-            },
+    /// Only the legacy admin can migrate, once. The account is grown to the
+    /// current layout (the admin pays the extra rent), the legacy totals,
+    /// last batch week and bumps are kept, and every new field gets the
+    /// value `initialize` would give it, with the legacy admin as the only
+    /// admin. Run it before `migrate_ngo` and `migrate_sponsor`, which need
+    /// the current layout.
+    pub fn migrate_config(ctx: Context<MigrateConfig>, timelock_delay: i64) -> Result<()> {
+        require!(
+            (1..=MAX_TIMELOCK_DELAY).contains(&timelock_delay),
+            EscrowError::InvalidTimelockDelay
         );
-        system_program::transfer(cpi_context, amount)?;
-
-        // Update config totals
-        let config = &mut ctx.accounts.config;
-        config.total_deposited = config
-            .total_deposited
-            .checked_add(amount)
-            .ok_or(EscrowError::Overflow)?;
 
-        // Update sponsor totals if registered
-        let sponsor_registry = &mut ctx.accounts.sponsor_registry;
-        let sponsor_pubkey = ctx.accounts.sponsor.key();
-        let sponsor_name = if let Some(sponsor) = sponsor_registry
-            .sponsors
-            .iter_mut()
-            .find(|s| s.pubkey == sponsor_pubkey)
-        {
-            sponsor.total_deposited = sponsor
-                .total_deposited
-                .checked_add(amount)
-                .ok_or(EscrowError::Overflow)?;
-            sponsor.deposit_count = sponsor
-                .deposit_count
-                .checked_add(1)
-                .ok_or(EscrowError::Overflow)?;
-            sponsor.last_deposit = Clock::get()?.unix_timestamp;
-            Some(sponsor.name.clone())
-        } else {
-            None
+        let info = ctx.accounts.config.to_account_info();
+        let legacy = {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.len() == 8 + LegacyConfig::INIT_SPACE
+                    && data.starts_with(Config::DISCRIMINATOR),
+                EscrowError::AccountMismatch
+            );
+            LegacyConfig::deserialize(&mut &data[8..])?
+        };
+        require_keys_eq!(legacy.admin, ctx.accounts.admin.key(), EscrowError::Unauthorized);
+
+        let space = 8 + Config::INIT_SPACE;
+        let rent = Rent::get()?.minimum_balance(space).saturating_sub(info.lamports());
+        if rent > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.admin.to_account_info(),
+                        to: info.clone(),
+                    },
+                ),
+                rent,
+            )?;
+        }
+        info.resize(space)?;
+
+        let config = Config {
+            admins: vec![legacy.admin],
+            threshold: 1,
+            proposal_count: 0,
+            pending_admin: None,
+            admin_change_count: 0,
+            timelock_delay,
+            timelock_disburse_threshold: 0,
+            paused: 0,
+            category_caps_bps: [10000; NGO_CATEGORY_COUNT],
+            total_deposited: legacy.total_deposited,
+            total_disbursed: legacy.total_disbursed,
+            total_points_redeemed: legacy.total_points_redeemed,
+            last_batch_week: legacy.last_batch_week,
+            earmark_count: 0,
+            earmarked_balance: 0,
+            campaign_count: 0,
+            matching_balance: 0,
+            ngo_count: 0,
+            withdrawal_notice: DEFAULT_WITHDRAWAL_NOTICE,
+            sponsor_shares: 0,
+            attributed_balance: 0,
+            total_withdrawn: 0,
+            lamports_per_1000_points: DEFAULT_LAMPORTS_PER_1000_POINTS,
+            price_feed: None,
+            max_price_age: 0,
+            max_price_confidence_bps: 0,
+            min_pledge_points: MIN_PLEDGE_POINTS,
+            max_pledge_points: 0,
+            roll_over_small_pledges: false,
+            vault_buffer: 0,
+            bump: legacy.bump,
+            vault_bump: legacy.vault_bump,
         };
+        config.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
 
-        emit!(DepositEvent {
-            sponsor: sponsor_pubkey,
-            sponsor_name,
-            amount,
+        emit!(ConfigMigratedEvent {
+            admin: legacy.admin,
+            total_deposited: legacy.total_deposited,
+            total_disbursed: legacy.total_disbursed,
+            last_batch_week: legacy.last_batch_week,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
-        msg!(
-            "Deposit received: {} lamports from {}",
-            amount,
-            sponsor_pubkey
-        );
+        msg!("Config migrated. Admin: {}", legacy.admin);
         Ok(())
     }
 
-    /// Register a sponsor (brand partner)
+    /// Create a proposal for a privileged action
     ///
-    /// Only the admin can register sponsors.
-    /// Registered sponsors have their deposits tracked publicly.
-    pub fn register_sponsor(ctx: Context<RegisterSponsor>, sponsor_pubkey: Pubkey, name: String) -> Result<()> {
-        let sponsor_registry = &mut ctx.accounts.sponsor_registry;
+    /// Only admins can create proposals. The proposer's approval is
+    /// recorded automatically. Once `threshold` admins have approved,
+    /// the matching privileged instruction can consume the proposal.
+    pub fn create_proposal(ctx: Context<CreateProposal>, action: ProposalAction) -> Result<()> {
+        action.validate()?;
+
+        let config = &mut ctx.accounts.config;
+        let proposal = &mut ctx.accounts.proposal;
+        let proposer = ctx.accounts.proposer.key();
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        proposal.id = config.proposal_count;
+        proposal.proposer = proposer;
+        proposal.action = action.clone();
+        proposal.approvals = vec![proposer];
+        proposal.status = ProposalStatus::Pending;
+        proposal.created_at = timestamp;
+        proposal.eta = None;
+        proposal.queued_at = None;
+        proposal.bump = ctx.bumps.proposal;
+
+        config.proposal_count = config
+            .proposal_count
+            .checked_add(1)
+            .ok_or(EscrowError::Overflow)?;
+
+        emit!(ProposalCreatedEvent {
+            proposal_id: proposal.id,
+            proposer,
+            action,
+            timestamp,
+        });
+
+        proposal.queue_if_approved(config, timestamp)?;
+
+        msg!("Proposal {} created by {}", proposal.id, proposer);
+        Ok(())
+    }
+
+    /// Approve a pending proposal
+    ///
+    /// Each admin can approve a proposal once.
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let proposal = &mut ctx.accounts.proposal;
+        let admin = ctx.accounts.admin.key();
 
-        // Check if sponsor already exists
         require!(
-            !sponsor_registry.sponsors.iter().any(|s| s.pubkey == sponsor_pubkey),
-            EscrowError::SponsorAlreadyExists
+            proposal.status == ProposalStatus::Pending,
+            EscrowError::ProposalNotPending
         );
-
-        // Check capacity
         require!(
-            sponsor_registry.sponsors.len() < MAX_SPONSORS,
-            EscrowError::SponsorRegistryFull
+            !proposal.approvals.contains(&admin),
+            EscrowError::ProposalAlreadyApproved
         );
 
-        // Validate name length
-        require!(name.len() <= 64, EscrowError::NameTooLong);
+        proposal.approvals.push(admin);
 
-        sponsor_registry.sponsors.push(SponsorEntry {
-            pubkey: sponsor_pubkey,
-            name: name.clone(),
-            total_deposited: 0,
-            deposit_count: 0,
-            last_deposit: 0,
-            is_verified: true,
+        let timestamp = Clock::get()?.unix_timestamp;
+        emit!(ProposalApprovedEvent {
+            proposal_id: proposal.id,
+            approver: admin,
+            approvals: proposal.approval_count(config) as u8,
+            threshold: config.threshold,
+            timestamp,
         });
 
-        emit!(SponsorRegisteredEvent {
-            sponsor: sponsor_pubkey,
-            name,
+        proposal.queue_if_approved(config, timestamp)?;
+
+        msg!("Proposal {} approved by {}", proposal.id, admin);
+        Ok(())
+    }
+
+    /// Cancel a pending proposal
+    ///
+    /// Any admin or auditor can cancel. Cancelling can only block an
+    /// action, never authorize one, so it does not require a quorum.
+    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        let is_auditor = ctx
+            .accounts
+            .authority_roles
+            .as_ref()
+            .is_some_and(|r| r.has(Role::Auditor));
+        require!(
+            ctx.accounts.config.is_admin(&authority) || is_auditor,
+            EscrowError::Unauthorized
+        );
+
+        let proposal = &mut ctx.accounts.proposal;
+
+        require!(
+            proposal.status == ProposalStatus::Pending,
+            EscrowError::ProposalNotPending
+        );
+
+        proposal.status = ProposalStatus::Cancelled;
+
+        emit!(ProposalCancelledEvent {
+            proposal_id: proposal.id,
+            cancelled_by: authority,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
-        msg!("Sponsor registered: {}", sponsor_pubkey);
+        msg!("Proposal {} cancelled", proposal.id);
         Ok(())
     }
 
-    /// Remove a sponsor from verified status
+    /// Replace the admin set and approval threshold
     ///
-    /// Only the admin can unverify sponsors.
-    /// Unverified sponsors can still deposit but won't show as verified partners.
-    pub fn remove_sponsor(ctx: Context<RemoveSponsor>, sponsor_pubkey: Pubkey) -> Result<()> {
-        let sponsor_registry = &mut ctx.accounts.sponsor_registry;
-
-        let sponsor = sponsor_registry
-            .sponsors
-            .iter_mut()
-            .find(|s| s.pubkey == sponsor_pubkey)
-            .ok_or(EscrowError::SponsorNotFound)?;
+    /// Requires an approved `SetAdmins` proposal. Any pending admin
+    /// handover is dropped, since it was proposed against the old set.
+    pub fn set_admins(ctx: Context<SetAdmins>, admins: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        validate_admin_set(&admins, threshold)?;
+
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::SetAdmins {
+                admins: admins.clone(),
+                threshold,
+            },
+            ctx.accounts.admin.key(),
+        )?;
 
-        sponsor.is_verified = false;
+        let config = &mut ctx.accounts.config;
+        config.admins = admins.clone();
+        config.threshold = threshold;
+        config.pending_admin = None;
 
-        emit!(SponsorRemovedEvent {
-            sponsor: sponsor_pubkey,
+        emit!(AdminsUpdatedEvent {
+            admins,
+            threshold,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
-        msg!("Sponsor unverified: {}", sponsor_pubkey);
+        msg!("Admin set updated. Threshold: {}", threshold);
         Ok(())
     }
 
-    /// Add an NGO to the whitelist
+    /// Propose handing one admin seat over to a new key
     ///
-    /// Only the admin can add NGOs.
-    /// NGOs must be whitelisted before they can receive disbursements.
-    pub fn add_ngo(ctx: Context<AddNgo>, ngo_pubkey: Pubkey, name: String) -> Result<()> {
-        let ngo_registry = &mut ctx.accounts.ngo_registry;
+    /// Requires an approved `ProposeAdmin` proposal. The handover only
+    /// takes effect once the new admin signs `accept_admin`.
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, old_admin: Pubkey, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::ProposeAdmin {
+                old_admin,
+                new_admin,
+            },
+            ctx.accounts.admin.key(),
+        )?;
 
-        // Check if NGO already exists
+        let config = &mut ctx.accounts.config;
         require!(
-            !ngo_registry.ngos.iter().any(|n| n.pubkey == ngo_pubkey),
-            EscrowError::NgoAlreadyExists
+            config.pending_admin.is_none(),
+            EscrowError::AdminHandoverPending
         );
+        require!(config.is_admin(&old_admin), EscrowError::AdminNotFound);
+        require!(!config.is_admin(&new_admin), EscrowError::DuplicateAdmin);
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        config.pending_admin = Some(PendingAdmin {
+            old_admin,
+            new_admin,
+            proposed_at: timestamp,
+        });
 
-        // Check capacity
-        require!(
-            ngo_registry.ngos.len() < MAX_NGOS,
-            EscrowError::NgoRegistryFull
-        );
+        emit!(AdminHandoverProposedEvent {
+            old_admin,
+            new_admin,
+            timestamp,
+        });
 
-        // Validate name length
-        require!(name.len() <= 64, EscrowError::NameTooLong);
+        msg!("Admin handover proposed: {} -> {}", old_admin, new_admin);
+        Ok(())
+    }
+
+    /// Accept a pending admin handover
+    ///
+    /// Must be signed by the pending admin. Replaces the old admin in the
+    /// admin set and records the change in an `AdminChangeRecord`. Fails
+    /// if the new key has become an admin since the handover was proposed.
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let pending = config
+            .pending_admin
+            .clone()
+            .ok_or(EscrowError::NoPendingAdmin)?;
+        require!(!config.is_admin(&pending.new_admin), EscrowError::DuplicateAdmin);
+
+        let index = config
+            .admins
+            .iter()
+            .position(|a| *a == pending.old_admin)
+            .ok_or(EscrowError::AdminNotFound)?;
+        config.admins[index] = pending.new_admin;
+        config.pending_admin = None;
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        let record = &mut ctx.accounts.admin_change_record;
+        record.change_id = config.admin_change_count;
+        record.old_admin = pending.old_admin;
+        record.new_admin = pending.new_admin;
+        record.proposed_at = pending.proposed_at;
+        record.accepted_at = timestamp;
+        record.bump = ctx.bumps.admin_change_record;
+
+        config.admin_change_count = config
+            .admin_change_count
+            .checked_add(1)
+            .ok_or(EscrowError::Overflow)?;
 
-        ngo_registry.ngos.push(NgoEntry {
-            pubkey: ngo_pubkey,
-            name: name.clone(),
-            total_received: 0,
-            is_active: true,
+        emit!(AdminChangedEvent {
+            change_id: record.change_id,
+            old_admin: pending.old_admin,
+            new_admin: pending.new_admin,
+            timestamp,
         });
 
-        emit!(NgoAddedEvent {
-            ngo: ngo_pubkey,
-            name,
+        msg!(
+            "Admin handover accepted: {} -> {}",
+            pending.old_admin,
+            pending.new_admin
+        );
+        Ok(())
+    }
+
+    /// Cancel a pending admin handover
+    ///
+    /// Any admin can cancel, as can the pending admin (declining the seat).
+    pub fn cancel_admin_handover(ctx: Context<CancelAdminHandover>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let pending = config
+            .pending_admin
+            .clone()
+            .ok_or(EscrowError::NoPendingAdmin)?;
+
+        let signer = ctx.accounts.signer.key();
+        require!(
+            config.is_admin(&signer) || signer == pending.new_admin,
+            EscrowError::Unauthorized
+        );
+
+        config.pending_admin = None;
+
+        emit!(AdminHandoverCancelledEvent {
+            old_admin: pending.old_admin,
+            new_admin: pending.new_admin,
+            cancelled_by: signer,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
-        msg!("NGO added to whitelist: {}", ngo_pubkey);
+        msg!("Admin handover cancelled by {}", signer);
         Ok(())
     }
 
-    /// Remove an NGO from active status
+    /// Grant an operational role to a key
     ///
-    /// Only the admin can deactivate NGOs.
-    /// Deactivated NGOs cannot receive new disbursements.
-    pub fn remove_ngo(ctx: Context<RemoveNgo>, ngo_pubkey: Pubkey) -> Result<()> {
-        let ngo_registry = &mut ctx.accounts.ngo_registry;
+    /// Requires an approved `GrantRole` proposal. Creates the holder's
+    /// role assignment on first grant.
+    pub fn grant_role(ctx: Context<GrantRole>, holder: Pubkey, role: Role) -> Result<()> {
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::GrantRole { holder, role },
+            ctx.accounts.admin.key(),
+        )?;
 
-        let ngo = ngo_registry
-            .ngos
-            .iter_mut()
-            .find(|n| n.pubkey == ngo_pubkey)
-            .ok_or(EscrowError::NgoNotFound)?;
+        let role_assignment = &mut ctx.accounts.role_assignment;
+        require!(!role_assignment.has(role), EscrowError::RoleAlreadyGranted);
 
-        ngo.is_active = false;
+        role_assignment.holder = holder;
+        role_assignment.roles |= role.bit();
+        role_assignment.bump = ctx.bumps.role_assignment;
 
-        emit!(NgoRemovedEvent {
-            ngo: ngo_pubkey,
+        emit!(RoleGrantedEvent {
+            holder,
+            role,
+            roles: role_assignment.roles,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
-        msg!("NGO deactivated: {}", ngo_pubkey);
+        msg!("Role {:?} granted to {}", role, holder);
         Ok(())
     }
 
-    /// Disburse funds from the escrow vault to a whitelisted NGO (single)
+    /// Revoke an operational role from a key
     ///
-    /// Only the admin can trigger disbursements.
-    /// The NGO must be active in the whitelist.
-    /// Emits a verifiable on-chain receipt with optional memo.
-    pub fn disburse(ctx: Context<Disburse>, amount: u64, memo: String) -> Result<()> {
-        require!(amount > 0, EscrowError::InvalidAmount);
-        require!(memo.len() <= 256, EscrowError::MemoTooLong);
+    /// Requires an approved `RevokeRole` proposal. The role assignment is
+    /// closed once the holder has no roles left.
+    pub fn revoke_role(ctx: Context<RevokeRole>, holder: Pubkey, role: Role) -> Result<()> {
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::RevokeRole { holder, role },
+            ctx.accounts.admin.key(),
+        )?;
 
-        let ngo_registry = &mut ctx.accounts.ngo_registry;
-        let ngo_pubkey = ctx.accounts.ngo.key();
+        let role_assignment = &mut ctx.accounts.role_assignment;
+        require!(role_assignment.has(role), EscrowError::MissingRole);
 
-        // Find and validate NGO
-        let ngo = ngo_registry
-            .ngos
-            .iter_mut()
-            .find(|n| n.pubkey == ngo_pubkey)
-            .ok_or(EscrowError::NgoNotFound)?;
+        role_assignment.roles &= !role.bit();
+        let roles = role_assignment.roles;
 
-        require!(ngo.is_active, EscrowError::NgoNotActive);
+        emit!(RoleRevokedEvent {
+            holder,
+            role,
+            roles,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        // Check vault has sufficient funds
-        let vault_balance = ctx.accounts.escrow_vault.lamports();
-        require!(vault_balance >= amount, EscrowError::InsufficientFunds);
+        if roles == 0 {
+            ctx.accounts
+                .role_assignment
+                .close(ctx.accounts.admin.to_account_info())?;
+        }
 
-        // Transfer from vault PDA to NGO using invoke_signed
-        let vault_bump = ctx.accounts.config.vault_bump;
-        let seeds = &[ESCROW_SEED, &[vault_bump]];
-        let signer_seeds = &[&seeds[..]];
+        msg!("Role {:?} revoked from {}", role, holder);
+        Ok(())
+    }
 
-        invoke_signed(
-            &anchor_lang::solana_program::system_instruction::transfer(
-                &ctx.accounts.escrow_vault.key(),
-                &ctx.accounts.ngo.key(),
-                amount,
-            ),
-            &[
-                ctx.accounts.escrow_vault.to_account_info(),
-                ctx.accounts.ngo.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-            signer_seeds,
+    /// Update the timelock applied to sensitive proposals
+    ///
+    /// Requires an approved `SetTimelock` proposal, which is itself
+    /// timelocked under the current delay.
+    pub fn set_timelock(ctx: Context<SetTimelock>, delay: i64, disburse_threshold: u64) -> Result<()> {
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::SetTimelock {
+                delay,
+                disburse_threshold,
+            },
+            ctx.accounts.admin.key(),
         )?;
 
-        // Update totals
-        ngo.total_received = ngo
-            .total_received
-            .checked_add(amount)
-            .ok_or(EscrowError::Overflow)?;
+        let config = &mut ctx.accounts.config;
+        let old_delay = config.timelock_delay;
+        config.timelock_delay = delay;
+        config.timelock_disburse_threshold = disburse_threshold;
+
+        emit!(TimelockUpdatedEvent {
+            old_delay,
+            new_delay: delay,
+            disburse_threshold,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Timelock delay set to {}s", delay);
+        Ok(())
+    }
+
+    /// Set the maximum share of a batch one cause category may receive
+    ///
+    /// Requires an approved `SetCategoryCap` proposal, which is timelocked
+    /// so sponsors can see a cap being loosened before it applies.
+    /// `max_share_bps` is in basis points of the batch's total points
+    /// (10000 = uncapped, the default).
+    pub fn set_category_cap(ctx: Context<SetCategoryCap>, category: NgoCategory, max_share_bps: u16) -> Result<()> {
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::SetCategoryCap {
+                category,
+                max_share_bps,
+            },
+            ctx.accounts.admin.key(),
+        )?;
 
         let config = &mut ctx.accounts.config;
-        config.total_disbursed = config
-            .total_disbursed
-            .checked_add(amount)
-            .ok_or(EscrowError::Overflow)?;
+        let old_share_bps = config.category_caps_bps[category.index()];
+        config.category_caps_bps[category.index()] = max_share_bps;
 
-        emit!(DisburseEvent {
-            ngo: ngo_pubkey,
-            amount,
-            memo,
+        emit!(CategoryCapUpdatedEvent {
+            category,
+            old_share_bps,
+            new_share_bps: max_share_bps,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
-        msg!("Disbursed {} lamports to NGO: {}", amount, ngo_pubkey);
+        msg!("Category cap for {:?} set to {} bps", category, max_share_bps);
         Ok(())
     }
 
-    /// Batch disburse funds to multiple NGOs based on user pledges
+    /// Set the buffer kept in the vault on top of its rent-exempt minimum
     ///
-    /// This is the weekly batch operation that:
-    /// 1. Takes aggregated pledge data from off-chain
-    /// 2. Applies pro-rata scaling if pledges exceed vault balance
-    /// 3. Transfers to all NGOs in one transaction
-    /// 4. Emits a comprehensive receipt for verification
+    /// Requires an approved `SetVaultBuffer` proposal, which is timelocked.
+    /// Disbursements, batches and sponsor withdrawals only pay out of the
+    /// balance above the rent-exempt minimum plus this buffer.
+    pub fn set_vault_buffer(ctx: Context<SetVaultBuffer>, buffer: u64) -> Result<()> {
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::SetVaultBuffer { buffer },
+            ctx.accounts.admin.key(),
+        )?;
+
+        let config = &mut ctx.accounts.config;
+        let old_buffer = config.vault_buffer;
+        config.vault_buffer = buffer;
+
+        emit!(VaultBufferUpdatedEvent {
+            old_buffer,
+            new_buffer: buffer,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Vault buffer set to {} lamports", buffer);
+        Ok(())
+    }
+
+    /// Set the points one NGO may be allocated in a batch
     ///
-    /// NGO accounts must be passed as remaining_accounts in the same order as allocations.
-    pub fn batch_disburse<'info>(
-        ctx: Context<'_, '_, 'info, 'info, BatchDisburse<'info>>,
-        week_id: u64,
-        allocations: Vec<BatchAllocation>,
+    /// Requires an approved `SetPledgeLimits` proposal, which is
+    /// timelocked. Allocations above `max_points` (0 = no maximum) fail
+    /// the batch. Allocations below `min_points` fail it too, unless
+    /// `roll_over` is set, in which case they are skipped and listed in
+    /// the batch event so the pledges can be carried into a later batch.
+    pub fn set_pledge_limits(
+        ctx: Context<SetPledgeLimits>,
+        min_points: u64,
+        max_points: u64,
+        roll_over: bool,
     ) -> Result<()> {
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::SetPledgeLimits {
+                min_points,
+                max_points,
+                roll_over,
+            },
+            ctx.accounts.admin.key(),
+        )?;
+
         let config = &mut ctx.accounts.config;
-        let ngo_registry = &mut ctx.accounts.ngo_registry;
+        config.min_pledge_points = min_points;
+        config.max_pledge_points = max_points;
+        config.roll_over_small_pledges = roll_over;
+
+        emit!(PledgeLimitsUpdatedEvent {
+            min_points,
+            max_points,
+            roll_over,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        // Validate week_id hasn't been processed
-        require!(
-            week_id > config.last_batch_week,
-            EscrowError::WeekAlreadyProcessed
+        msg!(
+            "Pledge limits set to {}..{} points (roll over: {})",
+            min_points,
+            max_points,
+            roll_over
         );
+        Ok(())
+    }
 
-        // Validate batch size
+    /// Change how long sponsor withdrawals wait after being requested
+    ///
+    /// Requires an approved `SetWithdrawalNotice` proposal, which is
+    /// timelocked. The new period applies to requests made afterwards.
+    pub fn set_withdrawal_notice(ctx: Context<SetWithdrawalNotice>, notice: i64) -> Result<()> {
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::SetWithdrawalNotice { notice },
+            ctx.accounts.admin.key(),
+        )?;
+
+        let config = &mut ctx.accounts.config;
+        let old_notice = config.withdrawal_notice;
+        config.withdrawal_notice = notice;
+
+        emit!(WithdrawalNoticeUpdatedEvent {
+            old_notice,
+            new_notice: notice,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Withdrawal notice set to {}s", notice);
+        Ok(())
+    }
+
+    /// Change the points-to-lamports conversion rate
+    ///
+    /// Requires an approved `SetConversionRate` proposal, which is
+    /// timelocked so a rate change is visible before batches use it.
+    /// Each `BatchDisburseEvent` records the rate it was paid at.
+    pub fn set_conversion_rate(ctx: Context<SetConversionRate>, lamports_per_1000_points: u64) -> Result<()> {
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::SetConversionRate {
+                lamports_per_1000_points,
+            },
+            ctx.accounts.admin.key(),
+        )?;
+
+        let config = &mut ctx.accounts.config;
+        let old_rate = config.lamports_per_1000_points;
+        config.lamports_per_1000_points = lamports_per_1000_points;
+
+        emit!(ConversionRateUpdatedEvent {
+            old_rate,
+            new_rate: lamports_per_1000_points,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!(
+            "Conversion rate set to {} lamports per 1000 points",
+            lamports_per_1000_points
+        );
+        Ok(())
+    }
+
+    /// Price batches from a SOL/USD feed instead of the fixed rate
+    ///
+    /// Requires an approved, timelocked `SetPriceFeed` proposal. While a
+    /// feed is set, `batch_disburse` converts points at 100 points = $1
+    /// using the feed's price, and fails rather than pay out against a
+    /// price older than `max_age` seconds or with a confidence interval
+    /// wider than `max_confidence_bps` of the price. `None` switches
+    /// back to `Config.lamports_per_1000_points`.
+    pub fn set_price_feed(
+        ctx: Context<SetPriceFeed>,
+        price_feed: Option<Pubkey>,
+        max_age: i64,
+        max_confidence_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::SetPriceFeed {
+                price_feed,
+                max_age,
+                max_confidence_bps,
+            },
+            ctx.accounts.admin.key(),
+        )?;
+
+        let config = &mut ctx.accounts.config;
+        let old_feed = config.price_feed;
+        config.price_feed = price_feed;
+        config.max_price_age = max_age;
+        config.max_price_confidence_bps = max_confidence_bps;
+
+        emit!(PriceFeedUpdatedEvent {
+            old_feed,
+            new_feed: price_feed,
+            max_age,
+            max_confidence_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Price feed set to {:?}", price_feed);
+        Ok(())
+    }
+
+    /// Write a price into the program-owned mock SOL/USD feed
+    ///
+    /// The mock uses the Pyth price account layout so localnet tests can
+    /// exercise oracle pricing. Only built with the `mock-oracle` feature,
+    /// and `SetPriceFeed` refuses to point `Config.price_feed` at it.
+    #[cfg(feature = "mock-oracle")]
+    pub fn write_mock_price_feed(
+        ctx: Context<WriteMockPriceFeed>,
+        price: i64,
+        conf: u64,
+        expo: i32,
+        publish_time: i64,
+    ) -> Result<()> {
+        let mut data = ctx.accounts.price_feed.try_borrow_mut_data()?;
+        data[0..4].copy_from_slice(&PYTH_MAGIC.to_le_bytes());
+        data[4..8].copy_from_slice(&PYTH_VERSION.to_le_bytes());
+        data[8..12].copy_from_slice(&PYTH_PRICE_ACCOUNT_TYPE.to_le_bytes());
+        data[12..16].copy_from_slice(&(PYTH_PRICE_ACCOUNT_LEN as u32).to_le_bytes());
+        data[20..24].copy_from_slice(&expo.to_le_bytes());
+        data[96..104].copy_from_slice(&publish_time.to_le_bytes());
+        data[208..216].copy_from_slice(&price.to_le_bytes());
+        data[216..224].copy_from_slice(&conf.to_le_bytes());
+        data[224..228].copy_from_slice(&PYTH_STATUS_TRADING.to_le_bytes());
+
+        msg!("Mock price feed set to {}e{} ± {}", price, expo, conf);
+        Ok(())
+    }
+
+    /// Pause one or more instruction classes (emergency circuit breaker)
+    ///
+    /// A single pauser can trip the breaker immediately; no proposal is
+    /// needed because pausing can only stop funds from moving.
+    /// `flags` is a bitmask of deposits (1), disbursements (2) and
+    /// registry changes (4).
+    pub fn pause(ctx: Context<Pause>, flags: u8) -> Result<()> {
         require!(
-            allocations.len() <= MAX_BATCH_SIZE,
-            EscrowError::BatchTooLarge
+            flags != 0 && flags & !PAUSE_ALL == 0,
+            EscrowError::InvalidPauseFlags
         );
-        require!(!allocations.is_empty(), EscrowError::EmptyBatch);
 
-        // Validate remaining accounts match allocations
+        let config = &mut ctx.accounts.config;
+        config.paused |= flags;
+
+        emit!(PausedEvent {
+            flags,
+            paused: config.paused,
+            pauser: ctx.accounts.pauser.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Paused flags {:#05b} (now {:#05b})", flags, config.paused);
+        Ok(())
+    }
+
+    /// Lift a pause on one or more instruction classes
+    ///
+    /// Requires an approved `Unpause` proposal so a single key cannot
+    /// resume operations after an incident.
+    pub fn unpause(ctx: Context<Unpause>, flags: u8) -> Result<()> {
         require!(
-            ctx.remaining_accounts.len() == allocations.len(),
-            EscrowError::AccountMismatch
+            flags != 0 && flags & !PAUSE_ALL == 0,
+            EscrowError::InvalidPauseFlags
         );
 
-        // Calculate total points pledged
-        let total_points: u64 = allocations
-            .iter()
-            .map(|a| a.points_pledged)
-            .try_fold(0u64, |acc, p| acc.checked_add(p))
-            .ok_or(EscrowError::Overflow)?;
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::Unpause { flags },
+            ctx.accounts.admin.key(),
+        )?;
+
+        let config = &mut ctx.accounts.config;
+        config.paused &= !flags;
+
+        emit!(UnpausedEvent {
+            flags,
+            paused: config.paused,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        require!(total_points > 0, EscrowError::InvalidAmount);
+        msg!("Unpaused flags {:#05b} (now {:#05b})", flags, config.paused);
+        Ok(())
+    }
 
-        // Calculate total lamports requested
-        let total_lamports_requested = points_to_lamports(total_points)?;
+    /// Deposit SOL into the escrow vault
+    ///
+    /// Any sponsor (brand partner) can deposit funds.
+    /// Funds are held in the vault PDA until disbursed to NGOs.
+    /// If the sponsor's ledger is passed (registered or anonymous), their
+    /// totals are updated; otherwise the deposit only counts toward
+    /// `total_deposited`.
+    pub fn deposit(ctx: Context<Deposit>, amount: u64, campaign_tag: Option<String>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PAUSE_DEPOSITS)?;
+        require!(amount > 0, EscrowError::InvalidAmount);
+        validate_campaign_tag(&campaign_tag)?;
 
-        // Get vault balance
-        let vault_balance = ctx.accounts.escrow_vault.lamports();
+        // Transfer SOL from sponsor to escrow vault
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.sponsor.to_account_info(),
+                to: ctx.accounts.escrow_vault.to_account_info(),
+            },
+        );
+        system_program::transfer(cpi_context, amount)?;
 
-        // Calculate pro-rata multiplier if vault is underfunded
-        // We use basis points (10000 = 100%) for precision
-        let pro_rata_bps: u16 = if total_lamports_requested > vault_balance {
-            // Pro-rata: everyone gets proportionally less
-            (vault_balance
-                .checked_mul(10000)
-                .ok_or(EscrowError::Overflow)?
-                .checked_div(total_lamports_requested)
-                .ok_or(EscrowError::Overflow)?) as u16
-        } else {
-            10000u16 // 100%
+        // Update config totals
+        let config = &mut ctx.accounts.config;
+        config.total_deposited = config
+            .total_deposited
+            .checked_add(amount)
+            .ok_or(EscrowError::Overflow)?;
+
+        // Update sponsor totals if a ledger was passed
+        let sponsor_pubkey = ctx.accounts.sponsor.key();
+        let timestamp = Clock::get()?.unix_timestamp;
+        let (sponsor_name, sequence) = match ctx.accounts.sponsor_account.as_mut() {
+            Some(sponsor) => {
+                let sequence = sponsor.record_deposit(amount, timestamp)?;
+                // Ledger deposits stay withdrawable until disbursed
+                let shares = config.issue_shares(amount)?;
+                sponsor.shares = sponsor.shares.checked_add(shares).ok_or(EscrowError::Overflow)?;
+                (sponsor.is_registered.then(|| sponsor.name.clone()), Some(sequence))
+            }
+            None => (None, None),
         };
 
-        // Prepare for transfers
-        let vault_bump = config.vault_bump;
-        let seeds = &[ESCROW_SEED, &[vault_bump]];
-        let signer_seeds = &[&seeds[..]];
+        if let Some(receipt) = ctx.accounts.receipt.as_mut() {
+            receipt.set_inner(DepositReceipt {
+                sponsor: sponsor_pubkey,
+                sequence: sequence.ok_or(EscrowError::ReceiptRequiresLedger)?,
+                amount,
+                mint: None,
+                campaign_tag,
+                created_at: timestamp,
+                bump: ctx.bumps.receipt.ok_or(EscrowError::ReceiptRequiresLedger)?,
+            });
+        }
 
-        let mut disbursement_details: Vec<DisbursementDetail> = Vec::new();
-        let mut total_disbursed_this_batch: u64 = 0;
+        emit!(DepositEvent {
+            sponsor: sponsor_pubkey,
+            sponsor_name,
+            amount,
+            timestamp,
+        });
 
-        // Process each allocation
-        for (i, allocation) in allocations.iter().enumerate() {
-            let ngo_account = &ctx.remaining_accounts[i];
+        msg!(
+            "Deposit received: {} lamports from {}",
+            amount,
+            sponsor_pubkey
+        );
+        Ok(())
+    }
 
-            // Validate NGO is in registry and active
-            let ngo_entry = ngo_registry
-                .ngos
-                .iter_mut()
-                .find(|n| n.pubkey == allocation.ngo)
+    /// Deposit funds restricted to one NGO or one cause category
+    ///
+    /// The lamports go into the shared vault, but the restriction and the
+    /// unspent balance are kept in a new `Earmark` account. Batches draw
+    /// on earmarks an NGO is eligible for before the unrestricted pool,
+    /// and earmarked lamports are never paid out as unrestricted funds.
+    /// An NGO restriction names the NGO's id, which is never reissued and
+    /// stays the same across wallet rotations, and requires the NGO's
+    /// current account to be passed and active.
+    pub fn deposit_earmarked(
+        ctx: Context<DepositEarmarked>,
+        amount: u64,
+        restriction: EarmarkRestriction,
+        campaign_tag: Option<String>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PAUSE_DEPOSITS)?;
+        require!(amount > 0, EscrowError::InvalidAmount);
+        validate_campaign_tag(&campaign_tag)?;
+        if let EarmarkRestriction::Ngo { ngo_id } = restriction {
+            let ngo_account = ctx
+                .accounts
+                .ngo_account
+                .as_ref()
                 .ok_or(EscrowError::NgoNotFound)?;
+            require!(ngo_account.id == ngo_id, EscrowError::NgoNotFound);
+        }
 
-            require!(ngo_entry.is_active, EscrowError::NgoNotActive);
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.sponsor.to_account_info(),
+                    to: ctx.accounts.escrow_vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
 
-            // Validate account matches allocation
-            require!(
-                ngo_account.key() == allocation.ngo,
-                EscrowError::AccountMismatch
-            );
+        let sponsor_pubkey = ctx.accounts.sponsor.key();
+        let timestamp = Clock::get()?.unix_timestamp;
+        let config = &mut ctx.accounts.config;
+        let earmark_id = config.earmark_count;
+        config.earmark_count = earmark_id.checked_add(1).ok_or(EscrowError::Overflow)?;
+        config.total_deposited = config
+            .total_deposited
+            .checked_add(amount)
+            .ok_or(EscrowError::Overflow)?;
+        config.earmarked_balance = config
+            .earmarked_balance
+            .checked_add(amount)
+            .ok_or(EscrowError::Overflow)?;
 
-            // Calculate actual amount (applying pro-rata if needed)
-            let base_amount = points_to_lamports(allocation.points_pledged)?;
-            let actual_amount = if pro_rata_bps < 10000 {
-                base_amount
-                    .checked_mul(pro_rata_bps as u64)
-                    .ok_or(EscrowError::Overflow)?
-                    .checked_div(10000)
-                    .ok_or(EscrowError::Overflow)?
-            } else {
-                base_amount
-            };
+        let sequence = match ctx.accounts.sponsor_account.as_mut() {
+            Some(sponsor) => Some(sponsor.record_deposit(amount, timestamp)?),
+            None => None,
+        };
 
-            if actual_amount > 0 {
-                // Transfer to NGO
-                invoke_signed(
-                    &anchor_lang::solana_program::system_instruction::transfer(
-                        &ctx.accounts.escrow_vault.key(),
-                        &ngo_account.key(),
-                        actual_amount,
-                    ),
-                    &[
-                        ctx.accounts.escrow_vault.to_account_info(),
-                        ngo_account.to_account_info(),
-                        ctx.accounts.system_program.to_account_info(),
-                    ],
-                    signer_seeds,
-                )?;
+        if let Some(receipt) = ctx.accounts.receipt.as_mut() {
+            receipt.set_inner(DepositReceipt {
+                sponsor: sponsor_pubkey,
+                sequence: sequence.ok_or(EscrowError::ReceiptRequiresLedger)?,
+                amount,
+                mint: None,
+                campaign_tag,
+                created_at: timestamp,
+                bump: ctx.bumps.receipt.ok_or(EscrowError::ReceiptRequiresLedger)?,
+            });
+        }
 
-                // Update NGO totals
-                ngo_entry.total_received = ngo_entry
-                    .total_received
-                    .checked_add(actual_amount)
-                    .ok_or(EscrowError::Overflow)?;
+        let earmark = &mut ctx.accounts.earmark;
+        earmark.id = earmark_id;
+        earmark.sponsor = sponsor_pubkey;
+        earmark.restriction = restriction;
+        earmark.amount = amount;
+        earmark.remaining = amount;
+        earmark.created_at = timestamp;
+        earmark.bump = ctx.bumps.earmark;
+
+        emit!(EarmarkCreatedEvent {
+            earmark_id,
+            sponsor: sponsor_pubkey,
+            restriction,
+            amount,
+            timestamp,
+        });
 
-                total_disbursed_this_batch = total_disbursed_this_batch
-                    .checked_add(actual_amount)
-                    .ok_or(EscrowError::Overflow)?;
+        msg!(
+            "Earmarked deposit {}: {} lamports from {}",
+            earmark_id,
+            amount,
+            sponsor_pubkey
+        );
+        Ok(())
+    }
 
-                disbursement_details.push(DisbursementDetail {
-                    ngo: allocation.ngo,
-                    points_pledged: allocation.points_pledged,
-                    amount_disbursed: actual_amount,
-                });
+    /// Return what is left of an earmark to its sponsor
+    ///
+    /// Requires an approved `RefundEarmark` proposal, for when the named
+    /// NGO is removed or the funds can no longer be spent as intended.
+    /// The unspent lamports are released from the earmarked balance and
+    /// sent back along with the account's rent.
+    pub fn refund_earmark(ctx: Context<RefundEarmark>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PAUSE_DISBURSEMENTS)?;
+        let earmark_id = ctx.accounts.earmark.id;
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::RefundEarmark { earmark_id },
+            ctx.accounts.admin.key(),
+        )?;
 
-                msg!(
-                    "Disbursed {} lamports to {} ({} points)",
-                    actual_amount,
-                    allocation.ngo,
-                    allocation.points_pledged
-                );
-            }
+        let refunded = ctx.accounts.earmark.remaining;
+        if refunded > 0 {
+            let vault_bump = ctx.accounts.config.vault_bump;
+            let seeds = &[ESCROW_SEED, &[vault_bump]];
+            let signer_seeds = &[&seeds[..]];
+
+            invoke_signed(
+                &anchor_lang::solana_program::system_instruction::transfer(
+                    &ctx.accounts.escrow_vault.key(),
+                    &ctx.accounts.sponsor.key(),
+                    refunded,
+                ),
+                &[
+                    ctx.accounts.escrow_vault.to_account_info(),
+                    ctx.accounts.sponsor.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+                signer_seeds,
+            )?;
         }
 
-        // Update config totals
-        config.total_disbursed = config
-            .total_disbursed
-            .checked_add(total_disbursed_this_batch)
+        let config = &mut ctx.accounts.config;
+        config.earmarked_balance = config
+            .earmarked_balance
+            .checked_sub(refunded)
             .ok_or(EscrowError::Overflow)?;
-
-        config.total_points_redeemed = config
-            .total_points_redeemed
-            .checked_add(total_points)
+        config.total_withdrawn = config
+            .total_withdrawn
+            .checked_add(refunded)
             .ok_or(EscrowError::Overflow)?;
 
-        config.last_batch_week = week_id;
-
-        // Emit comprehensive batch event
-        emit!(BatchDisburseEvent {
-            week_id,
-            total_points_pledged: total_points,
-            total_amount_requested: total_lamports_requested,
-            total_amount_disbursed: total_disbursed_this_batch,
-            pro_rata_bps,
-            num_ngos: allocations.len() as u8,
-            disbursements: disbursement_details,
+        let earmark = &ctx.accounts.earmark;
+        emit!(EarmarkRefundedEvent {
+            earmark_id,
+            sponsor: earmark.sponsor,
+            restriction: earmark.restriction,
+            drawn: earmark.amount - refunded,
+            refunded,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
-        msg!(
-            "Batch disbursement complete: {} lamports to {} NGOs (week {})",
-            total_disbursed_this_batch,
-            allocations.len(),
-            week_id
-        );
-
+        msg!("Earmark {} refunded: {} lamports", earmark_id, refunded);
         Ok(())
     }
 
-    /// Get the current escrow status (view function via simulation)
-    pub fn get_status(ctx: Context<GetStatus>) -> Result<()> {
-        let config = &ctx.accounts.config;
-        let vault_balance = ctx.accounts.escrow_vault.lamports();
+    /// Fund a matching campaign for a set of NGOs
+    ///
+    /// The sponsor deposits `cap` lamports up front. Between `starts_at`
+    /// and `ends_at`, each batch that includes the campaign adds
+    /// `match_bps` of a listed NGO's base disbursement on top, until the
+    /// cap is used up. The funds are reserved in the vault like earmarks,
+    /// and whatever is left can be reclaimed with `close_matching_campaign`
+    /// once the campaign has ended. `remaining_accounts` must hold the
+    /// `NgoAccount` PDA of each listed NGO, in order.
+    pub fn create_matching_campaign<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateMatchingCampaign<'info>>,
+        ngos: Vec<Pubkey>,
+        match_bps: u16,
+        cap: u64,
+        starts_at: i64,
+        ends_at: i64,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PAUSE_DEPOSITS)?;
+        require!(cap > 0, EscrowError::InvalidAmount);
+        require!(match_bps > 0, EscrowError::InvalidMatchRatio);
+        require!(
+            !ngos.is_empty() && ngos.len() <= MAX_CAMPAIGN_NGOS,
+            EscrowError::InvalidCampaignNgos
+        );
+        let timestamp = Clock::get()?.unix_timestamp;
+        require!(
+            starts_at < ends_at && ends_at > timestamp,
+            EscrowError::InvalidCampaignWindow
+        );
 
-        msg!("=== Ecoscore Escrow Status ===");
-        msg!("Admin: {}", config.admin);
-        msg!("Total Deposited: {} lamports", config.total_deposited);
-        msg!("Total Disbursed: {} lamports", config.total_disbursed);
-        msg!("Total Points Redeemed: {}", config.total_points_redeemed);
-        msg!("Last Batch Week: {}", config.last_batch_week);
-        msg!("Current Vault Balance: {} lamports", vault_balance);
+        // Every listed NGO must be whitelisted, and listed only once
+        require!(
+            ctx.remaining_accounts.len() == ngos.len(),
+            EscrowError::AccountMismatch
+        );
+        for (i, wallet) in ngos.iter().enumerate() {
+            require!(!ngos[..i].contains(wallet), EscrowError::InvalidCampaignNgos);
+            load_ngo_account(&ctx.remaining_accounts[i], wallet, ctx.program_id)?;
+        }
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.sponsor.to_account_info(),
+                    to: ctx.accounts.escrow_vault.to_account_info(),
+                },
+            ),
+            cap,
+        )?;
+
+        let sponsor_pubkey = ctx.accounts.sponsor.key();
+        let config = &mut ctx.accounts.config;
+        let campaign_id = config.campaign_count;
+        config.campaign_count = campaign_id.checked_add(1).ok_or(EscrowError::Overflow)?;
+        config.matching_balance = config
+            .matching_balance
+            .checked_add(cap)
+            .ok_or(EscrowError::Overflow)?;
+
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.id = campaign_id;
+        campaign.sponsor = sponsor_pubkey;
+        campaign.ngos = ngos.clone();
+        campaign.match_bps = match_bps;
+        campaign.cap = cap;
+        campaign.matched = 0;
+        campaign.starts_at = starts_at;
+        campaign.ends_at = ends_at;
+        campaign.created_at = timestamp;
+        campaign.bump = ctx.bumps.campaign;
+
+        emit!(MatchingCampaignCreatedEvent {
+            campaign_id,
+            sponsor: sponsor_pubkey,
+            ngos,
+            match_bps,
+            cap,
+            starts_at,
+            ends_at,
+            timestamp,
+        });
 
+        msg!(
+            "Matching campaign {}: up to {} lamports from {}",
+            campaign_id,
+            cap,
+            sponsor_pubkey
+        );
         Ok(())
     }
-}
 
-// =============================================================================
-// HELPER FUNCTIONS
-// =============================================================================
+    /// Close an ended matching campaign, returning unused funds to its sponsor
+    ///
+    /// Only the sponsor that funded the campaign can close it, and only
+    /// after `ends_at`. The unmatched balance is released from the vault
+    /// reserve and sent back along with the account's rent.
+    pub fn close_matching_campaign(ctx: Context<CloseMatchingCampaign>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PAUSE_DISBURSEMENTS)?;
+        let timestamp = Clock::get()?.unix_timestamp;
+        let campaign = &ctx.accounts.campaign;
+        require!(timestamp >= campaign.ends_at, EscrowError::CampaignNotEnded);
+
+        let refunded = campaign.remaining();
+        if refunded > 0 {
+            let vault_bump = ctx.accounts.config.vault_bump;
+            let seeds = &[ESCROW_SEED, &[vault_bump]];
+            let signer_seeds = &[&seeds[..]];
+
+            invoke_signed(
+                &anchor_lang::solana_program::system_instruction::transfer(
+                    &ctx.accounts.escrow_vault.key(),
+                    &ctx.accounts.sponsor.key(),
+                    refunded,
+                ),
+                &[
+                    ctx.accounts.escrow_vault.to_account_info(),
+                    ctx.accounts.sponsor.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+                signer_seeds,
+            )?;
+        }
 
-/// Convert points to lamports using the fixed conversion rate
-fn points_to_lamports(points: u64) -> Result<u64> {
-    // 1000 points = LAMPORTS_PER_1000_POINTS lamports
-    points
-        .checked_mul(LAMPORTS_PER_1000_POINTS)
-        .ok_or(EscrowError::Overflow.into())
-        .map(|v| v / 1000)
-}
+        let config = &mut ctx.accounts.config;
+        config.matching_balance = config
+            .matching_balance
+            .checked_sub(refunded)
+            .ok_or(EscrowError::Overflow)?;
 
-// =============================================================================
-// ACCOUNT STRUCTURES
-// =============================================================================
+        emit!(MatchingCampaignClosedEvent {
+            campaign_id: campaign.id,
+            sponsor: campaign.sponsor,
+            matched: campaign.matched,
+            refunded,
+            timestamp,
+        });
 
-/// Configuration account storing admin and totals
-#[account]
+        msg!(
+            "Matching campaign {} closed: {} lamports refunded",
+            campaign.id,
+            refunded
+        );
+        Ok(())
+    }
+
+    /// Close a deposit receipt, returning its rent to the sponsor
+    ///
+    /// Receipts are only a convenience copy of the deposit for the
+    /// sponsor's records; closing one does not affect any totals.
+    pub fn close_deposit_receipt(ctx: Context<CloseDepositReceipt>) -> Result<()> {
+        msg!(
+            "Deposit receipt {} closed for {}",
+            ctx.accounts.receipt.sequence,
+            ctx.accounts.sponsor.key()
+        );
+        Ok(())
+    }
+
+    /// Ask to withdraw unallocated funds back to the sponsor
+    ///
+    /// Only SOL deposited through `deposit` with the sponsor's ledger can
+    /// be withdrawn, less the sponsor's pro-rata share of everything
+    /// disbursed since. The request locks in a number of shares, not
+    /// lamports: batches keep priority during the notice period, and
+    /// the payout is valued again when it executes. One request per
+    /// sponsor can be pending at a time.
+    pub fn request_withdrawal(ctx: Context<RequestWithdrawal>, amount: u64) -> Result<()> {
+        require!(amount > 0, EscrowError::InvalidAmount);
+
+        let config = &ctx.accounts.config;
+        let sponsor = &ctx.accounts.sponsor_account;
+        require!(
+            amount <= config.shares_value(sponsor.shares)?,
+            EscrowError::InsufficientSponsorBalance
+        );
+        let shares = config.shares_for(amount)?.min(sponsor.shares);
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        let executable_at = timestamp
+            .checked_add(config.withdrawal_notice)
+            .ok_or(EscrowError::Overflow)?;
+
+        let withdrawal = &mut ctx.accounts.withdrawal;
+        withdrawal.sponsor = ctx.accounts.sponsor.key();
+        withdrawal.amount = amount;
+        withdrawal.shares = shares;
+        withdrawal.requested_at = timestamp;
+        withdrawal.executable_at = executable_at;
+        withdrawal.bump = ctx.bumps.withdrawal;
+
+        emit!(WithdrawalRequestedEvent {
+            sponsor: withdrawal.sponsor,
+            amount,
+            shares,
+            executable_at,
+            timestamp,
+        });
+
+        msg!(
+            "Withdrawal of {} lamports requested by {}, executable at {}",
+            amount,
+            withdrawal.sponsor,
+            executable_at
+        );
+        Ok(())
+    }
+
+    /// Pay out a withdrawal request once its notice period has passed
+    ///
+    /// The sponsor receives the current value of the requested shares,
+    /// which is less than the requested amount if batches disbursed
+    /// unrestricted funds in the meantime.
+    pub fn execute_withdrawal(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PAUSE_DISBURSEMENTS)?;
+        let timestamp = Clock::get()?.unix_timestamp;
+        let withdrawal = &ctx.accounts.withdrawal;
+        require!(
+            timestamp >= withdrawal.executable_at,
+            EscrowError::WithdrawalNoticeNotElapsed
+        );
+
+        let shares = withdrawal.shares;
+        let amount = ctx.accounts.config.shares_value(shares)?;
+        let vault_balance = ctx.accounts.escrow_vault.lamports();
+        let unrestricted = ctx.accounts.config.unrestricted_balance(vault_balance)?;
+        require!(unrestricted >= amount, EscrowError::InsufficientFunds);
+
+        if amount > 0 {
+            let vault_bump = ctx.accounts.config.vault_bump;
+            let seeds = &[ESCROW_SEED, &[vault_bump]];
+            let signer_seeds = &[&seeds[..]];
+
+            invoke_signed(
+                &anchor_lang::solana_program::system_instruction::transfer(
+                    &ctx.accounts.escrow_vault.key(),
+                    &ctx.accounts.sponsor.key(),
+                    amount,
+                ),
+                &[
+                    ctx.accounts.escrow_vault.to_account_info(),
+                    ctx.accounts.sponsor.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+                signer_seeds,
+            )?;
+        }
+
+        let config = &mut ctx.accounts.config;
+        config.redeem_shares(shares, amount)?;
+        config.total_withdrawn = config
+            .total_withdrawn
+            .checked_add(amount)
+            .ok_or(EscrowError::Overflow)?;
+
+        let sponsor = &mut ctx.accounts.sponsor_account;
+        sponsor.shares = sponsor.shares.checked_sub(shares).ok_or(EscrowError::Overflow)?;
+        sponsor.total_withdrawn = sponsor
+            .total_withdrawn
+            .checked_add(amount)
+            .ok_or(EscrowError::Overflow)?;
+
+        emit!(WithdrawalExecutedEvent {
+            sponsor: sponsor.wallet,
+            requested_amount: withdrawal.amount,
+            amount,
+            shares,
+            timestamp,
+        });
+
+        msg!("Withdrew {} lamports to {}", amount, sponsor.wallet);
+        Ok(())
+    }
+
+    /// Cancel a pending withdrawal request
+    pub fn cancel_withdrawal(ctx: Context<CancelWithdrawal>) -> Result<()> {
+        let withdrawal = &ctx.accounts.withdrawal;
+
+        emit!(WithdrawalCancelledEvent {
+            sponsor: withdrawal.sponsor,
+            amount: withdrawal.amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Withdrawal cancelled by {}", withdrawal.sponsor);
+        Ok(())
+    }
+
+    /// Open an anonymous deposit ledger for the signing depositor
+    ///
+    /// Unregistered depositors can opt in to per-wallet tracking. The
+    /// ledger keeps its history if the sponsor is registered later.
+    pub fn open_sponsor_ledger(ctx: Context<OpenSponsorLedger>) -> Result<()> {
+        let sponsor_pubkey = ctx.accounts.sponsor.key();
+        let sponsor_account = &mut ctx.accounts.sponsor_account;
+        sponsor_account.wallet = sponsor_pubkey;
+        sponsor_account.name = String::new();
+        sponsor_account.total_deposited = 0;
+        sponsor_account.deposit_count = 0;
+        sponsor_account.last_deposit = 0;
+        sponsor_account.is_registered = false;
+        sponsor_account.is_verified = false;
+        sponsor_account.status_reason = None;
+        sponsor_account.status_changed_at = 0;
+        sponsor_account.shares = 0;
+        sponsor_account.total_withdrawn = 0;
+        sponsor_account.bump = ctx.bumps.sponsor_account;
+
+        emit!(SponsorLedgerOpenedEvent {
+            sponsor: sponsor_pubkey,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Anonymous ledger opened for {}", sponsor_pubkey);
+        Ok(())
+    }
+
+    /// Register a sponsor (brand partner)
+    ///
+    /// Requires an approved `RegisterSponsor` proposal.
+    /// Registered sponsors have their deposits tracked publicly.
+    /// An existing anonymous ledger is upgraded in place.
+    pub fn register_sponsor(ctx: Context<RegisterSponsor>, sponsor_pubkey: Pubkey, name: String) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PAUSE_REGISTRY)?;
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::RegisterSponsor {
+                sponsor: sponsor_pubkey,
+                name: name.clone(),
+            },
+            ctx.accounts.operator.key(),
+        )?;
+
+        let sponsor_account = &mut ctx.accounts.sponsor_account;
+
+        // Check if sponsor already exists
+        require!(
+            !sponsor_account.is_registered,
+            EscrowError::SponsorAlreadyExists
+        );
+
+        // Validate name length
+        require!(name.len() <= 64, EscrowError::NameTooLong);
+
+        // A fresh account has no history; an anonymous ledger keeps its totals
+        sponsor_account.wallet = sponsor_pubkey;
+        sponsor_account.name = name.clone();
+        sponsor_account.is_registered = true;
+        sponsor_account.is_verified = true;
+        sponsor_account.status_reason = None;
+        sponsor_account.status_changed_at = Clock::get()?.unix_timestamp;
+        sponsor_account.bump = ctx.bumps.sponsor_account;
+
+        emit!(SponsorRegisteredEvent {
+            sponsor: sponsor_pubkey,
+            name,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Sponsor registered: {}", sponsor_pubkey);
+        Ok(())
+    }
+
+    /// Remove a sponsor from verified status
+    ///
+    /// Requires an approved `RemoveSponsor` proposal.
+    /// Unverified sponsors can still deposit but won't show as verified partners.
+    /// The reason is stored on the sponsor account and emitted.
+    pub fn remove_sponsor(ctx: Context<RemoveSponsor>, sponsor_pubkey: Pubkey, reason: StatusReason) -> Result<()> {
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::RemoveSponsor {
+                sponsor: sponsor_pubkey,
+                reason,
+            },
+            ctx.accounts.operator.key(),
+        )?;
+
+        let sponsor = &mut ctx.accounts.sponsor_account;
+        require!(sponsor.is_registered, EscrowError::SponsorNotFound);
+        require!(sponsor.is_verified, EscrowError::SponsorNotVerified);
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        sponsor.is_verified = false;
+        sponsor.status_reason = Some(reason);
+        sponsor.status_changed_at = timestamp;
+
+        emit!(SponsorRemovedEvent {
+            sponsor: sponsor_pubkey,
+            reason,
+            timestamp,
+        });
+
+        msg!("Sponsor unverified: {} ({:?})", sponsor_pubkey, reason);
+        Ok(())
+    }
+
+    /// Restore verified status to a previously unverified sponsor
+    ///
+    /// Requires an approved `ReverifySponsor` proposal.
+    /// The reason is stored on the sponsor account and emitted.
+    pub fn reverify_sponsor(ctx: Context<ReverifySponsor>, sponsor_pubkey: Pubkey, reason: StatusReason) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PAUSE_REGISTRY)?;
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::ReverifySponsor {
+                sponsor: sponsor_pubkey,
+                reason,
+            },
+            ctx.accounts.operator.key(),
+        )?;
+
+        let sponsor = &mut ctx.accounts.sponsor_account;
+        require!(sponsor.is_registered, EscrowError::SponsorNotFound);
+        require!(!sponsor.is_verified, EscrowError::SponsorAlreadyVerified);
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        sponsor.is_verified = true;
+        sponsor.status_reason = Some(reason);
+        sponsor.status_changed_at = timestamp;
+
+        emit!(SponsorReverifiedEvent {
+            sponsor: sponsor_pubkey,
+            reason,
+            timestamp,
+        });
+
+        msg!("Sponsor re-verified: {} ({:?})", sponsor_pubkey, reason);
+        Ok(())
+    }
+
+    /// Add an NGO to the whitelist
+    ///
+    /// Requires an approved `AddNgo` proposal and the NGO wallet's
+    /// signature, proving the key is controlled by someone.
+    /// NGOs must be whitelisted before they can receive disbursements.
+    /// Creates the NGO's own account, so the whitelist has no size limit.
+    pub fn add_ngo(ctx: Context<AddNgo>, ngo_pubkey: Pubkey, name: String) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PAUSE_REGISTRY)?;
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::AddNgo {
+                ngo: ngo_pubkey,
+                name: name.clone(),
+            },
+            ctx.accounts.operator.key(),
+        )?;
+
+        let ngo_account = &mut ctx.accounts.ngo_account;
+
+        // Check if NGO already exists
+        require!(
+            ngo_account.wallet == Pubkey::default(),
+            EscrowError::NgoAlreadyExists
+        );
+
+        // Validate name length
+        require!(name.len() <= 64, EscrowError::NameTooLong);
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        let ngo_id = ctx.accounts.config.issue_ngo_id()?;
+        ngo_account.whitelist(
+            ngo_id,
+            ngo_pubkey,
+            NgoProfile::named(name.clone()),
+            timestamp,
+            ctx.bumps.ngo_account,
+        );
+
+        emit!(NgoAddedEvent {
+            ngo: ngo_pubkey,
+            ngo_id,
+            name,
+            timestamp,
+        });
+
+        msg!("NGO added to whitelist: {}", ngo_pubkey);
+        Ok(())
+    }
+
+    /// Apply to be whitelisted (signed by the NGO wallet)
+    ///
+    /// Creates a pending application holding the NGO's proposed profile.
+    /// An admin proposal then approves it, or a registry manager rejects it.
+    pub fn apply_ngo(ctx: Context<ApplyNgo>, profile: NgoProfile) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PAUSE_REGISTRY)?;
+        profile.validate()?;
+
+        let ngo_pubkey = ctx.accounts.ngo.key();
+        let timestamp = Clock::get()?.unix_timestamp;
+        let application = &mut ctx.accounts.application;
+        application.wallet = ngo_pubkey;
+        application.profile = profile.clone();
+        application.applied_at = timestamp;
+        application.bump = ctx.bumps.application;
+
+        emit!(NgoAppliedEvent {
+            ngo: ngo_pubkey,
+            profile,
+            timestamp,
+        });
+
+        msg!("NGO application submitted: {}", ngo_pubkey);
+        Ok(())
+    }
+
+    /// Approve a pending NGO application
+    ///
+    /// Requires an approved `ApproveNgoApplication` proposal, which is
+    /// timelocked like `AddNgo`. Whitelists the NGO with the profile it
+    /// applied with and refunds the application rent to the NGO.
+    pub fn approve_ngo_application(ctx: Context<ApproveNgoApplication>, ngo_pubkey: Pubkey) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PAUSE_REGISTRY)?;
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::ApproveNgoApplication { ngo: ngo_pubkey },
+            ctx.accounts.operator.key(),
+        )?;
+
+        let profile = ctx.accounts.application.profile.clone();
+        let timestamp = Clock::get()?.unix_timestamp;
+        let ngo_id = ctx.accounts.config.issue_ngo_id()?;
+        ctx.accounts.ngo_account.whitelist(
+            ngo_id,
+            ngo_pubkey,
+            profile.clone(),
+            timestamp,
+            ctx.bumps.ngo_account,
+        );
+
+        emit!(NgoAddedEvent {
+            ngo: ngo_pubkey,
+            ngo_id,
+            name: profile.name,
+            timestamp,
+        });
+
+        msg!("NGO application approved: {}", ngo_pubkey);
+        Ok(())
+    }
+
+    /// Reject a pending NGO application
+    ///
+    /// A registry manager can reject without a proposal, since rejecting
+    /// can only keep an address off the whitelist. The application rent
+    /// is refunded to the NGO, which may apply again.
+    pub fn reject_ngo_application(ctx: Context<RejectNgoApplication>, ngo_pubkey: Pubkey, reason: StatusReason) -> Result<()> {
+        emit!(NgoApplicationRejectedEvent {
+            ngo: ngo_pubkey,
+            reason,
+            rejected_by: ctx.accounts.operator.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("NGO application rejected: {} ({:?})", ngo_pubkey, reason);
+        Ok(())
+    }
+
+    /// Remove an NGO from active status
+    ///
+    /// Requires an approved `RemoveNgo` proposal.
+    /// Deactivated NGOs cannot receive new disbursements.
+    /// The reason is stored on the NGO account and emitted.
+    pub fn remove_ngo(ctx: Context<RemoveNgo>, ngo_pubkey: Pubkey, reason: StatusReason) -> Result<()> {
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::RemoveNgo {
+                ngo: ngo_pubkey,
+                reason,
+            },
+            ctx.accounts.operator.key(),
+        )?;
+
+        let ngo_account = &mut ctx.accounts.ngo_account;
+        require!(ngo_account.is_active, EscrowError::NgoNotActive);
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        ngo_account.is_active = false;
+        ngo_account.status_reason = Some(reason);
+        ngo_account.status_changed_at = timestamp;
+
+        emit!(NgoRemovedEvent {
+            ngo: ngo_pubkey,
+            reason,
+            timestamp,
+        });
+
+        msg!("NGO deactivated: {} ({:?})", ngo_pubkey, reason);
+        Ok(())
+    }
+
+    /// Return a deactivated NGO to active status
+    ///
+    /// Requires an approved `ReactivateNgo` proposal, which is timelocked
+    /// like `AddNgo`. The reason is stored on the NGO account and emitted.
+    pub fn reactivate_ngo(ctx: Context<ReactivateNgo>, ngo_pubkey: Pubkey, reason: StatusReason) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PAUSE_REGISTRY)?;
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::ReactivateNgo {
+                ngo: ngo_pubkey,
+                reason,
+            },
+            ctx.accounts.operator.key(),
+        )?;
+
+        let ngo_account = &mut ctx.accounts.ngo_account;
+        require!(!ngo_account.is_active, EscrowError::NgoAlreadyActive);
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        ngo_account.is_active = true;
+        ngo_account.status_reason = Some(reason);
+        ngo_account.status_changed_at = timestamp;
+
+        emit!(NgoReactivatedEvent {
+            ngo: ngo_pubkey,
+            reason,
+            timestamp,
+        });
+
+        msg!("NGO reactivated: {} ({:?})", ngo_pubkey, reason);
+        Ok(())
+    }
+
+    /// Update an NGO's public profile
+    ///
+    /// Requires an approved `UpdateNgoProfile` proposal. Replaces the name,
+    /// website, metadata URI and hash, category and country code in one go.
+    pub fn update_ngo_profile(ctx: Context<UpdateNgoProfile>, ngo_pubkey: Pubkey, profile: NgoProfile) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PAUSE_REGISTRY)?;
+        profile.validate()?;
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::UpdateNgoProfile {
+                ngo: ngo_pubkey,
+                profile: profile.clone(),
+            },
+            ctx.accounts.operator.key(),
+        )?;
+
+        let ngo_account = &mut ctx.accounts.ngo_account;
+        let old_profile = ngo_account.profile();
+        ngo_account.set_profile(profile.clone());
+
+        emit!(NgoProfileUpdatedEvent {
+            ngo: ngo_pubkey,
+            old_profile,
+            new_profile: profile,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("NGO profile updated: {}", ngo_pubkey);
+        Ok(())
+    }
+
+    /// Renew an NGO's verification
+    ///
+    /// Requires an approved `RenewNgoVerification` proposal. Sets
+    /// `verified_until` to a future time no more than two years ahead;
+    /// NGOs past their expiry cannot receive disbursements until renewed.
+    pub fn renew_ngo_verification(
+        ctx: Context<RenewNgoVerification>,
+        ngo_pubkey: Pubkey,
+        verified_until: i64,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PAUSE_REGISTRY)?;
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::RenewNgoVerification {
+                ngo: ngo_pubkey,
+                verified_until,
+            },
+            ctx.accounts.operator.key(),
+        )?;
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        require!(
+            verified_until > timestamp
+                && verified_until <= timestamp.saturating_add(MAX_VERIFICATION_PERIOD),
+            EscrowError::InvalidVerificationExpiry
+        );
+
+        let ngo_account = &mut ctx.accounts.ngo_account;
+        let previous_verified_until = ngo_account.verified_until;
+        ngo_account.verified_until = verified_until;
+
+        emit!(NgoVerificationRenewedEvent {
+            ngo: ngo_pubkey,
+            previous_verified_until,
+            verified_until,
+            timestamp,
+        });
+
+        msg!("NGO verification renewed: {} until {}", ngo_pubkey, verified_until);
+        Ok(())
+    }
+
+    /// Move an NGO to a new payout wallet
+    ///
+    /// Requires an approved `RotateNgoWallet` proposal, which is timelocked
+    /// like `AddNgo`, and the signatures of the NGO's current and new
+    /// wallets. Only active NGOs can rotate. The NGO's profile and
+    /// cumulative stats move to the account for the new wallet and the old
+    /// account is closed, leaving a `RetiredNgoWallet` marker behind.
+    pub fn rotate_ngo_wallet(ctx: Context<RotateNgoWallet>, new_wallet: Pubkey) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PAUSE_REGISTRY)?;
+        let old_wallet = ctx.accounts.ngo.key();
+        require_keys_neq!(old_wallet, new_wallet, EscrowError::SameWallet);
+
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::RotateNgoWallet {
+                old_wallet,
+                new_wallet,
+            },
+            ctx.accounts.admin.key(),
+        )?;
+
+        let mut ngo = (*ctx.accounts.old_ngo_account).clone();
+        ngo.wallet = new_wallet;
+        ngo.rotation_count = ngo
+            .rotation_count
+            .checked_add(1)
+            .ok_or(EscrowError::Overflow)?;
+        ngo.bump = ctx.bumps.new_ngo_account;
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        emit!(NgoWalletRotatedEvent {
+            original_wallet: ngo.original_wallet,
+            old_wallet,
+            new_wallet,
+            rotation_count: ngo.rotation_count,
+            total_received: ngo.total_received,
+            timestamp,
+        });
+
+        ctx.accounts.new_ngo_account.set_inner(ngo);
+        ctx.accounts.retired_wallet.set_inner(RetiredNgoWallet {
+            wallet: old_wallet,
+            retired_at: timestamp,
+            bump: ctx.bumps.retired_wallet,
+        });
+
+        msg!("NGO wallet rotated: {} -> {}", old_wallet, new_wallet);
+        Ok(())
+    }
+
+    /// Disburse funds from the escrow vault to a whitelisted NGO (single)
+    ///
+    /// Requires an approved `Disburse` proposal for this NGO and amount.
+    /// The NGO's account must be active.
+    /// Emits a verifiable on-chain receipt with optional memo.
+    pub fn disburse(ctx: Context<Disburse>, amount: u64, memo: String) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PAUSE_DISBURSEMENTS)?;
+        require!(amount > 0, EscrowError::InvalidAmount);
+        require!(memo.len() <= 256, EscrowError::MemoTooLong);
+
+        let ngo_pubkey = ctx.accounts.ngo.key();
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::Disburse {
+                ngo: ngo_pubkey,
+                amount,
+            },
+            ctx.accounts.operator.key(),
+        )?;
+
+        ctx.accounts
+            .ngo_account
+            .require_eligible(Clock::get()?.unix_timestamp)?;
+
+        // Check the unrestricted pool has sufficient funds
+        let vault_balance = ctx.accounts.escrow_vault.lamports();
+        let unrestricted = ctx.accounts.config.unrestricted_balance(vault_balance)?;
+        require!(unrestricted >= amount, EscrowError::InsufficientFunds);
+
+        // Transfer from vault PDA to NGO using invoke_signed
+        let vault_bump = ctx.accounts.config.vault_bump;
+        let seeds = &[ESCROW_SEED, &[vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        invoke_signed(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                &ctx.accounts.escrow_vault.key(),
+                &ctx.accounts.ngo.key(),
+                amount,
+            ),
+            &[
+                ctx.accounts.escrow_vault.to_account_info(),
+                ctx.accounts.ngo.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+
+        // Update totals
+        let timestamp = Clock::get()?.unix_timestamp;
+        ctx.accounts.ngo_account.record_disbursement(amount, timestamp)?;
+
+        let config = &mut ctx.accounts.config;
+        config.record_unrestricted_disbursement(amount, unrestricted)?;
+        config.total_disbursed = config
+            .total_disbursed
+            .checked_add(amount)
+            .ok_or(EscrowError::Overflow)?;
+
+        emit!(DisburseEvent {
+            ngo: ngo_pubkey,
+            amount,
+            memo,
+            vault_reserve: config.vault_reserve()?,
+            timestamp,
+        });
+
+        msg!("Disbursed {} lamports to NGO: {}", amount, ngo_pubkey);
+        Ok(())
+    }
+
+    /// Batch disburse funds to multiple NGOs based on user pledges
+    ///
+    /// This is the weekly batch operation that:
+    /// 1. Takes aggregated pledge data from off-chain
+    /// 2. Applies pro-rata scaling if pledges exceed vault balance
+    /// 3. Transfers to all NGOs in one transaction
+    /// 4. Emits a comprehensive receipt for verification
+    ///
+    /// Requires an approved `BatchDisburse` proposal for exactly this
+    /// `week_id` and `allocations`.
+    ///
+    /// For each allocation, in order, remaining_accounts must contain the NGO
+    /// wallet followed by its `NgoAccount` PDA. Any `Earmark` accounts to
+    /// draw on follow the pairs; each NGO's amount is taken from the
+    /// earmarks it is eligible for, in the order passed, before the
+    /// unrestricted pool. Pro-rata only scales the unrestricted part.
+    ///
+    /// When `Config.price_feed` is set, the feed must be passed as
+    /// `price_feed` and points are converted at its current SOL/USD price.
+    ///
+    /// Allocations outside `Config`'s pledge limits fail the batch, except
+    /// those under the minimum while rollover is on: they are skipped (their
+    /// accounts must still be passed) and reported as `rolled_over`. If
+    /// every allocation rolls over, nothing is sent but the week is still
+    /// recorded and the event emitted.
+    pub fn batch_disburse<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchDisburse<'info>>,
+        week_id: u64,
+        allocations: Vec<BatchAllocation>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PAUSE_DISBURSEMENTS)?;
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::BatchDisburse {
+                week_id,
+                allocations: allocations.clone(),
+            },
+            ctx.accounts.operator.key(),
+        )?;
+
+        let config = &mut ctx.accounts.config;
+
+        // Validate week_id hasn't been processed
+        require!(
+            week_id > config.last_batch_week,
+            EscrowError::WeekAlreadyProcessed
+        );
+
+        // Validate batch size
+        require!(
+            allocations.len() <= MAX_BATCH_SIZE,
+            EscrowError::BatchTooLarge
+        );
+        require!(!allocations.is_empty(), EscrowError::EmptyBatch);
+
+        // Validate remaining accounts cover allocations (wallet + PDA each),
+        // followed by any earmarks and matching campaigns
+        require!(
+            ctx.remaining_accounts.len() >= allocations.len() * 2,
+            EscrowError::AccountMismatch
+        );
+        let (mut earmarks, mut campaigns) = load_earmarks_and_campaigns(
+            &ctx.remaining_accounts[allocations.len() * 2..],
+            ctx.program_id,
+        )?;
+
+        // Apply pledge limits, then total the points being paid
+        let (payable, rolled_over) = config.split_by_pledge_limits(&allocations)?;
+        let total_points: u64 = payable
+            .iter()
+            .map(|(_, a)| a.points_pledged)
+            .try_fold(0u64, |acc, p| acc.checked_add(p))
+            .ok_or(EscrowError::Overflow)?;
+
+        // A batch whose allocations all roll over pays nothing but still
+        // records the week
+        require!(payable.is_empty() || total_points > 0, EscrowError::InvalidAmount);
+
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        // Snapshot the conversion rate for this batch: from the price feed
+        // when one is configured, otherwise the fixed rate
+        let price = match config.price_feed {
+            Some(feed) => {
+                let price_feed = ctx
+                    .accounts
+                    .price_feed
+                    .as_ref()
+                    .ok_or(EscrowError::PriceFeedRequired)?;
+                require_keys_eq!(price_feed.key(), feed, EscrowError::AccountMismatch);
+                Some(PriceSnapshot::read(price_feed, config, timestamp)?)
+            }
+            None => None,
+        };
+        let lamports_per_1000_points = match &price {
+            Some(price) => price.lamports_per_1000_points()?,
+            None => config.lamports_per_1000_points,
+        };
+        let total_lamports_requested = points_to_units(total_points, lamports_per_1000_points)?;
+
+        // First pass: validate NGOs and draw what we can from earmarks
+        let mut ngo_accounts = Vec::with_capacity(payable.len());
+        let mut base_amounts = Vec::with_capacity(payable.len());
+        let mut earmarked_amounts = Vec::with_capacity(payable.len());
+        let mut total_from_earmarks: u64 = 0;
+        for (i, allocation) in payable.iter() {
+            // Validate account matches allocation
+            require!(
+                ctx.remaining_accounts[2 * i].key() == allocation.ngo,
+                EscrowError::AccountMismatch
+            );
+
+            // Validate NGO is whitelisted, active and currently verified
+            let ngo_account =
+                load_ngo_account(&ctx.remaining_accounts[2 * i + 1], &allocation.ngo, ctx.program_id)?;
+            ngo_account.require_eligible(timestamp)?;
+
+            let base_amount = points_to_units(allocation.points_pledged, lamports_per_1000_points)?;
+            let drawn = draw_from_earmarks(&mut earmarks, &ngo_account, base_amount, week_id, timestamp)?;
+            total_from_earmarks = total_from_earmarks
+                .checked_add(drawn)
+                .ok_or(EscrowError::Overflow)?;
+
+            ngo_accounts.push(ngo_account);
+            base_amounts.push(base_amount);
+            earmarked_amounts.push(drawn);
+        }
+
+        // Scale the unrestricted parts down to exactly what the unrestricted
+        // pool holds if it is underfunded
+        let vault_balance = ctx.accounts.escrow_vault.lamports();
+        let vault_reserve = config.vault_reserve()?;
+        let unrestricted = config.unrestricted_balance(vault_balance)?;
+        let requested_amounts: Vec<u64> = base_amounts
+            .iter()
+            .zip(&earmarked_amounts)
+            .map(|(base, earmarked)| base - earmarked)
+            .collect();
+        let unrestricted_requested: u64 = requested_amounts.iter().sum();
+        let (unrestricted_amounts, dust_allocated) = pro_rata_shares(&requested_amounts, unrestricted)?;
+        let pro_rata_bps = pro_rata_bps(unrestricted_requested, unrestricted)?;
+
+        // Prepare for transfers
+        let vault_bump = config.vault_bump;
+        let seeds = &[ESCROW_SEED, &[vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let mut disbursement_details: Vec<DisbursementDetail> = Vec::new();
+        let mut category_totals: Vec<CategoryTotal> = Vec::new();
+        let mut total_disbursed_this_batch: u64 = 0;
+        let mut total_matched: u64 = 0;
+
+        // Second pass: pay each allocation plus any campaign matches
+        for (j, ((i, allocation), mut ngo_account)) in payable.iter().zip(ngo_accounts).enumerate() {
+            let ngo_wallet = &ctx.remaining_accounts[2 * i];
+
+            // Earmarked part in full, unrestricted part with pro-rata applied
+            let actual_amount = earmarked_amounts[j]
+                .checked_add(unrestricted_amounts[j])
+                .ok_or(EscrowError::Overflow)?;
+            // Matches come from their own reserved funds, so they follow the
+            // pledged points rather than what the unrestricted pool could pay
+            let matched = draw_matches(&mut campaigns, &ngo_account, base_amounts[j], week_id, timestamp)?;
+            let paid = actual_amount.checked_add(matched).ok_or(EscrowError::Overflow)?;
+
+            add_category_total(
+                &mut category_totals,
+                ngo_account.category,
+                allocation.points_pledged,
+                paid,
+            )?;
+
+            if paid > 0 {
+                // Transfer to NGO
+                invoke_signed(
+                    &anchor_lang::solana_program::system_instruction::transfer(
+                        &ctx.accounts.escrow_vault.key(),
+                        &ngo_wallet.key(),
+                        paid,
+                    ),
+                    &[
+                        ctx.accounts.escrow_vault.to_account_info(),
+                        ngo_wallet.to_account_info(),
+                        ctx.accounts.system_program.to_account_info(),
+                    ],
+                    signer_seeds,
+                )?;
+
+                // Update NGO totals
+                ngo_account.record_disbursement(paid, timestamp)?;
+                ngo_account.exit(ctx.program_id)?;
+
+                total_disbursed_this_batch = total_disbursed_this_batch
+                    .checked_add(paid)
+                    .ok_or(EscrowError::Overflow)?;
+                total_matched = total_matched.checked_add(matched).ok_or(EscrowError::Overflow)?;
+
+                disbursement_details.push(DisbursementDetail {
+                    ngo: allocation.ngo,
+                    points_pledged: allocation.points_pledged,
+                    amount_disbursed: actual_amount,
+                    amount_matched: matched,
+                });
+
+                msg!(
+                    "Disbursed {} lamports (+{} matched) to {} ({} points)",
+                    actual_amount,
+                    matched,
+                    allocation.ngo,
+                    allocation.points_pledged
+                );
+            }
+        }
+
+        // Enforce per-category caps on share of points (the whole batch
+        // reverts, transfers included, if any category is over its cap)
+        config.require_within_category_caps(&mut category_totals, total_points)?;
+
+        // Persist earmark balances
+        for earmark in earmarks.iter() {
+            earmark.exit(ctx.program_id)?;
+        }
+        config.earmarked_balance = config
+            .earmarked_balance
+            .checked_sub(total_from_earmarks)
+            .ok_or(EscrowError::Overflow)?;
+
+        // Persist campaign balances
+        for campaign in campaigns.iter() {
+            campaign.exit(ctx.program_id)?;
+        }
+        config.matching_balance = config
+            .matching_balance
+            .checked_sub(total_matched)
+            .ok_or(EscrowError::Overflow)?;
+
+        // Charge sponsors' withdrawable balances for the unrestricted part
+        let total_unrestricted = total_disbursed_this_batch - total_from_earmarks - total_matched;
+        config.record_unrestricted_disbursement(total_unrestricted, unrestricted)?;
+
+        // Update config totals
+        config.total_disbursed = config
+            .total_disbursed
+            .checked_add(total_disbursed_this_batch)
+            .ok_or(EscrowError::Overflow)?;
+
+        config.total_points_redeemed = config
+            .total_points_redeemed
+            .checked_add(total_points)
+            .ok_or(EscrowError::Overflow)?;
+
+        config.last_batch_week = week_id;
+
+        // Emit comprehensive batch event
+        emit!(BatchDisburseEvent {
+            week_id,
+            total_points_pledged: total_points,
+            total_amount_requested: total_lamports_requested,
+            total_amount_disbursed: total_disbursed_this_batch,
+            total_from_earmarks,
+            total_matched,
+            lamports_per_1000_points,
+            price,
+            pro_rata_bps,
+            pro_rata_available: unrestricted.min(unrestricted_requested),
+            pro_rata_requested: unrestricted_requested,
+            dust_allocated,
+            vault_reserve,
+            num_ngos: payable.len() as u8,
+            disbursements: disbursement_details,
+            category_totals,
+            rolled_over,
+            timestamp,
+        });
+
+        msg!(
+            "Batch disbursement complete: {} lamports to {} NGOs (week {})",
+            total_disbursed_this_batch,
+            payable.len(),
+            week_id
+        );
+
+        Ok(())
+    }
+
+    /// Accept a token (e.g. USDC) for deposits and disbursements
+    ///
+    /// Requires an approved `AddMint` proposal. Creates the mint's stats
+    /// account and its vault: the associated token account owned by the
+    /// escrow vault PDA. `units_per_1000_points` is the amount of the
+    /// token, in base units, paid out per 1000 points.
+    ///
+    /// Both SPL Token and Token-2022 mints are accepted. For Token-2022
+    /// mints with a transfer fee, deposits are credited with the amount
+    /// that actually arrived and disbursements are grossed up so NGOs
+    /// receive the full amount owed.
+    pub fn add_mint(ctx: Context<AddMint>, units_per_1000_points: u64) -> Result<()> {
+        let mint = ctx.accounts.mint.key();
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::AddMint {
+                mint,
+                units_per_1000_points,
+            },
+            ctx.accounts.admin.key(),
+        )?;
+
+        let mint_config = &mut ctx.accounts.mint_config;
+        mint_config.mint = mint;
+        mint_config.vault = ctx.accounts.vault.key();
+        mint_config.token_program = ctx.accounts.token_program.key();
+        mint_config.decimals = ctx.accounts.mint.decimals;
+        mint_config.units_per_1000_points = units_per_1000_points;
+        mint_config.total_deposited = 0;
+        mint_config.total_disbursed = 0;
+        mint_config.total_fees_withheld = 0;
+        mint_config.total_points_redeemed = 0;
+        mint_config.last_batch_week = 0;
+        mint_config.bump = ctx.bumps.mint_config;
+
+        emit!(MintAddedEvent {
+            mint,
+            vault: mint_config.vault,
+            units_per_1000_points,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Mint accepted: {}", mint);
+        Ok(())
+    }
+
+    /// Deposit tokens into the mint's vault
+    ///
+    /// Anyone can deposit an accepted token. Totals are tracked per mint
+    /// on its `MintConfig`; SOL totals in `Config` are unaffected.
+    /// The deposit is credited with the vault's actual balance change, so
+    /// transfer fees withheld by Token-2022 mints are never counted.
+    /// A sponsor ledger, if passed, counts the deposit but not its amount
+    /// (ledger totals are in lamports).
+    pub fn deposit_token(ctx: Context<DepositToken>, amount: u64, campaign_tag: Option<String>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PAUSE_DEPOSITS)?;
+        require!(amount > 0, EscrowError::InvalidAmount);
+        validate_campaign_tag(&campaign_tag)?;
+
+        let balance_before = ctx.accounts.vault.amount;
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.sponsor_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.sponsor.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+        ctx.accounts.vault.reload()?;
+        let amount_received = ctx
+            .accounts
+            .vault
+            .amount
+            .checked_sub(balance_before)
+            .ok_or(EscrowError::Overflow)?;
+        require!(amount_received > 0, EscrowError::InvalidAmount);
+
+        let mint_config = &mut ctx.accounts.mint_config;
+        mint_config.total_deposited = mint_config
+            .total_deposited
+            .checked_add(amount_received)
+            .ok_or(EscrowError::Overflow)?;
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        let sequence = match ctx.accounts.sponsor_account.as_mut() {
+            Some(sponsor) => Some(sponsor.count_deposit(timestamp)?),
+            None => None,
+        };
+
+        if let Some(receipt) = ctx.accounts.receipt.as_mut() {
+            receipt.set_inner(DepositReceipt {
+                sponsor: ctx.accounts.sponsor.key(),
+                sequence: sequence.ok_or(EscrowError::ReceiptRequiresLedger)?,
+                amount: amount_received,
+                mint: Some(mint_config.mint),
+                campaign_tag,
+                created_at: timestamp,
+                bump: ctx.bumps.receipt.ok_or(EscrowError::ReceiptRequiresLedger)?,
+            });
+        }
+
+        emit!(TokenDepositEvent {
+            sponsor: ctx.accounts.sponsor.key(),
+            mint: mint_config.mint,
+            amount,
+            amount_received,
+            timestamp,
+        });
+
+        msg!(
+            "Token deposit received: {} of {} from {} ({} sent)",
+            amount_received,
+            mint_config.mint,
+            ctx.accounts.sponsor.key(),
+            amount
+        );
+        Ok(())
+    }
+
+    /// Disburse tokens to a single NGO (manual override)
+    ///
+    /// Requires an approved `DisburseToken` proposal. The recipient is the
+    /// owner of `ngo_token_account`, which must be a whitelisted NGO.
+    /// `amount` is what the NGO receives; any transfer fee is paid by the
+    /// vault on top.
+    pub fn disburse_token(ctx: Context<DisburseToken>, amount: u64, memo: String) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PAUSE_DISBURSEMENTS)?;
+        require!(amount > 0, EscrowError::InvalidAmount);
+        require!(memo.len() <= 256, EscrowError::MemoTooLong);
+
+        let mint = ctx.accounts.mint_config.mint;
+        let ngo_pubkey = ctx.accounts.ngo_token_account.owner;
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::DisburseToken {
+                mint,
+                ngo: ngo_pubkey,
+                amount,
+            },
+            ctx.accounts.operator.key(),
+        )?;
+
+        let clock = Clock::get()?;
+        let timestamp = clock.unix_timestamp;
+        ctx.accounts.ngo_account.require_eligible(timestamp)?;
+
+        let mint_info = ctx.accounts.mint.to_account_info();
+        let fee = transfer_fee_for_net(&mint_info, amount, clock.epoch)?;
+        let gross_amount = amount.checked_add(fee).ok_or(EscrowError::Overflow)?;
+        require!(
+            ctx.accounts.vault.amount >= gross_amount,
+            EscrowError::InsufficientFunds
+        );
+
+        let vault_bump = ctx.accounts.config.vault_bump;
+        let seeds = &[ESCROW_SEED, &[vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.vault.to_account_info(),
+                    mint: mint_info,
+                    to: ctx.accounts.ngo_token_account.to_account_info(),
+                    authority: ctx.accounts.escrow_vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            gross_amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        let mint_config = &mut ctx.accounts.mint_config;
+        mint_config.record_disbursement(amount, fee)?;
+
+        emit!(TokenDisburseEvent {
+            mint,
+            ngo: ngo_pubkey,
+            amount,
+            fee,
+            memo,
+            timestamp,
+        });
+
+        msg!("Disbursed {} of {} to NGO: {}", amount, mint, ngo_pubkey);
+        Ok(())
+    }
+
+    /// Weekly batch disbursement in tokens
+    ///
+    /// Requires an approved `BatchDisburseToken` proposal. Works like
+    /// `batch_disburse`, converting points at the mint's rate and applying
+    /// pro-rata against the token vault. Weeks are tracked per mint.
+    /// Amounts are what each NGO receives; transfer fees are paid by the
+    /// vault on top and count against its balance for pro-rata.
+    ///
+    /// remaining_accounts: for each allocation, in order, the NGO's token
+    /// account for this mint, then its `NgoAccount` PDA.
+    pub fn batch_disburse_token<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchDisburseToken<'info>>,
+        week_id: u64,
+        allocations: Vec<BatchAllocation>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PAUSE_DISBURSEMENTS)?;
+        let mint = ctx.accounts.mint_config.mint;
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::BatchDisburseToken {
+                mint,
+                week_id,
+                allocations: allocations.clone(),
+            },
+            ctx.accounts.operator.key(),
+        )?;
+
+        require!(
+            week_id > ctx.accounts.mint_config.last_batch_week,
+            EscrowError::WeekAlreadyProcessed
+        );
+        require!(
+            allocations.len() <= MAX_BATCH_SIZE,
+            EscrowError::BatchTooLarge
+        );
+        require!(!allocations.is_empty(), EscrowError::EmptyBatch);
+        require!(
+            ctx.remaining_accounts.len() == allocations.len() * 2,
+            EscrowError::AccountMismatch
+        );
+
+        let (payable, rolled_over) = ctx.accounts.config.split_by_pledge_limits(&allocations)?;
+        let total_points: u64 = payable
+            .iter()
+            .map(|(_, a)| a.points_pledged)
+            .try_fold(0u64, |acc, p| acc.checked_add(p))
+            .ok_or(EscrowError::Overflow)?;
+        require!(payable.is_empty() || total_points > 0, EscrowError::InvalidAmount);
+
+        let clock = Clock::get()?;
+        let timestamp = clock.unix_timestamp;
+        let mint_info = ctx.accounts.mint.to_account_info();
+        let decimals = ctx.accounts.mint.decimals;
+        let units_per_1000_points = ctx.accounts.mint_config.units_per_1000_points;
+
+        // Pro-rata against what the vault must send, fees included
+        let mut total_amount_requested: u64 = 0;
+        let mut gross_requested: Vec<u64> = Vec::with_capacity(payable.len());
+        for (_, allocation) in payable.iter() {
+            let amount = points_to_units(allocation.points_pledged, units_per_1000_points)?;
+            let fee = transfer_fee_for_net(&mint_info, amount, clock.epoch)?;
+            total_amount_requested = total_amount_requested
+                .checked_add(amount)
+                .ok_or(EscrowError::Overflow)?;
+            gross_requested.push(amount.checked_add(fee).ok_or(EscrowError::Overflow)?);
+        }
+        let total_gross_requested = gross_requested
+            .iter()
+            .try_fold(0u64, |acc, &g| acc.checked_add(g))
+            .ok_or(EscrowError::Overflow)?;
+        let vault_balance = ctx.accounts.vault.amount;
+        let pro_rata_bps = pro_rata_bps(total_gross_requested, vault_balance)?;
+        let (gross_shares, dust_allocated) = pro_rata_shares(&gross_requested, vault_balance)?;
+
+        let vault_bump = ctx.accounts.config.vault_bump;
+        let seeds = &[ESCROW_SEED, &[vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let mut disbursement_details: Vec<DisbursementDetail> = Vec::new();
+        let mut category_totals: Vec<CategoryTotal> = Vec::new();
+        let mut total_disbursed_this_batch: u64 = 0;
+        let mut total_fees: u64 = 0;
+
+        for ((i, allocation), &gross_amount) in payable.iter().zip(gross_shares.iter()) {
+            let ngo_token_info = &ctx.remaining_accounts[2 * i];
+            load_ngo_token_account(ngo_token_info, &allocation.ngo, &mint)?;
+
+            let ngo_account =
+                load_ngo_account(&ctx.remaining_accounts[2 * i + 1], &allocation.ngo, ctx.program_id)?;
+            ngo_account.require_eligible(timestamp)?;
+
+            // The mint withholds its fee out of whatever the vault sends
+            let fee = transfer_fee_on_gross(&mint_info, gross_amount, clock.epoch)?;
+            let actual_amount = gross_amount.checked_sub(fee).ok_or(EscrowError::Overflow)?;
+
+            add_category_total(
+                &mut category_totals,
+                ngo_account.category,
+                allocation.points_pledged,
+                actual_amount,
+            )?;
+
+            if actual_amount > 0 {
+                token_interface::transfer_checked(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        TransferChecked {
+                            from: ctx.accounts.vault.to_account_info(),
+                            mint: mint_info.clone(),
+                            to: ngo_token_info.clone(),
+                            authority: ctx.accounts.escrow_vault.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    gross_amount,
+                    decimals,
+                )?;
+
+                total_disbursed_this_batch = total_disbursed_this_batch
+                    .checked_add(actual_amount)
+                    .ok_or(EscrowError::Overflow)?;
+                total_fees = total_fees.checked_add(fee).ok_or(EscrowError::Overflow)?;
+
+                disbursement_details.push(DisbursementDetail {
+                    ngo: allocation.ngo,
+                    points_pledged: allocation.points_pledged,
+                    amount_disbursed: actual_amount,
+                    amount_matched: 0,
+                });
+            }
+        }
+
+        ctx.accounts
+            .config
+            .require_within_category_caps(&mut category_totals, total_points)?;
+
+        let mint_config = &mut ctx.accounts.mint_config;
+        mint_config.record_disbursement(total_disbursed_this_batch, total_fees)?;
+        mint_config.total_points_redeemed = mint_config
+            .total_points_redeemed
+            .checked_add(total_points)
+            .ok_or(EscrowError::Overflow)?;
+        mint_config.last_batch_week = week_id;
+
+        emit!(TokenBatchDisburseEvent {
+            mint,
+            week_id,
+            total_points_pledged: total_points,
+            total_amount_requested,
+            total_amount_disbursed: total_disbursed_this_batch,
+            total_fees,
+            pro_rata_bps,
+            pro_rata_available: vault_balance.min(total_gross_requested),
+            pro_rata_requested: total_gross_requested,
+            dust_allocated,
+            num_ngos: payable.len() as u8,
+            disbursements: disbursement_details,
+            category_totals,
+            rolled_over,
+            timestamp,
+        });
+
+        msg!(
+            "Token batch complete: {} of {} to {} NGOs (week {})",
+            total_disbursed_this_batch,
+            mint,
+            allocations.len(),
+            week_id
+        );
+        Ok(())
+    }
+
+    /// Archive a deactivated NGO
+    ///
+    /// Requires an approved `ArchiveNgo` proposal. Emits the NGO's lifetime
+    /// totals and closes its account, returning the rent to the executing
+    /// admin, who pays for a `RetiredNgoWallet` marker instead. `Config`
+    /// totals are left untouched, so historical figures still include
+    /// everything the NGO received.
+    pub fn archive_ngo(ctx: Context<ArchiveNgo>, ngo_pubkey: Pubkey) -> Result<()> {
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::ArchiveNgo { ngo: ngo_pubkey },
+            ctx.accounts.admin.key(),
+        )?;
+
+        let ngo_account = &ctx.accounts.ngo_account;
+        require!(!ngo_account.is_active, EscrowError::NgoStillActive);
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        emit!(NgoArchivedEvent {
+            ngo: ngo_pubkey,
+            original_wallet: ngo_account.original_wallet,
+            name: ngo_account.name.clone(),
+            total_received: ngo_account.total_received,
+            disbursement_count: ngo_account.disbursement_count,
+            added_at: ngo_account.added_at,
+            status_reason: ngo_account.status_reason,
+            timestamp,
+        });
+
+        ctx.accounts.retired_wallet.set_inner(RetiredNgoWallet {
+            wallet: ngo_pubkey,
+            retired_at: timestamp,
+            bump: ctx.bumps.retired_wallet,
+        });
+
+        msg!("NGO archived: {}", ngo_pubkey);
+        Ok(())
+    }
+
+    /// Archive an unverified sponsor
+    ///
+    /// Requires an approved `ArchiveSponsor` proposal. Only registered
+    /// sponsors that have been unverified can be archived; anonymous
+    /// ledgers belong to their depositors. A pending withdrawal request
+    /// must be executed or cancelled first. Emits the lifetime totals and
+    /// closes the account, returning the rent to the executing admin.
+    pub fn archive_sponsor(ctx: Context<ArchiveSponsor>, sponsor_pubkey: Pubkey) -> Result<()> {
+        ctx.accounts.proposal.execute(
+            &ctx.accounts.config,
+            &ProposalAction::ArchiveSponsor {
+                sponsor: sponsor_pubkey,
+            },
+            ctx.accounts.admin.key(),
+        )?;
+
+        let sponsor = &ctx.accounts.sponsor_account;
+        require!(sponsor.is_registered, EscrowError::SponsorNotFound);
+        require!(!sponsor.is_verified, EscrowError::SponsorStillVerified);
+
+        // Any unwithdrawn balance stays in the vault for NGOs
+        let config = &mut ctx.accounts.config;
+        let forfeited = config.shares_value(sponsor.shares)?;
+        config.redeem_shares(sponsor.shares, forfeited)?;
+
+        emit!(SponsorArchivedEvent {
+            sponsor: sponsor_pubkey,
+            name: sponsor.name.clone(),
+            total_deposited: sponsor.total_deposited,
+            deposit_count: sponsor.deposit_count,
+            last_deposit: sponsor.last_deposit,
+            status_reason: sponsor.status_reason,
+            forfeited,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Sponsor archived: {}", sponsor_pubkey);
+        Ok(())
+    }
+
+    /// Move one NGO from the legacy `NgoRegistry` into its own account
+    ///
+    /// Only a registry manager can migrate. The entry's name, total and
+    /// active flag are carried over unchanged, so no proposal is needed.
+    /// If the NGO was whitelisted again after the upgrade, the legacy total
+    /// is added to its existing account and its current profile and status
+    /// are kept. A wallet the NGO has since rotated away from or been
+    /// archived under comes back inactive, so it needs a `ReactivateNgo`
+    /// proposal to receive funds again. The legacy registry is closed once
+    /// its last entry has moved.
+    pub fn migrate_ngo(ctx: Context<MigrateNgo>, ngo_pubkey: Pubkey) -> Result<()> {
+        let ngo_registry = &mut ctx.accounts.ngo_registry;
+        let index = ngo_registry
+            .ngos
+            .iter()
+            .position(|n| n.pubkey == ngo_pubkey)
+            .ok_or(EscrowError::NgoNotFound)?;
+        let entry = ngo_registry.ngos.remove(index);
+        let remaining = ngo_registry.ngos.len() as u32;
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        let ngo_account = &mut ctx.accounts.ngo_account;
+        let merged = ngo_account.wallet != Pubkey::default();
+        let retired = !ctx.accounts.retired_wallet.data_is_empty();
+        if merged {
+            ngo_account.total_received = ngo_account
+                .total_received
+                .checked_add(entry.total_received)
+                .ok_or(EscrowError::Overflow)?;
+        } else {
+            ngo_account.whitelist(
+                ctx.accounts.config.issue_ngo_id()?,
+                entry.pubkey,
+                NgoProfile::named(entry.name),
+                timestamp,
+                ctx.bumps.ngo_account,
+            );
+            ngo_account.total_received = entry.total_received;
+            ngo_account.is_active = entry.is_active && !retired;
+        }
+
+        emit!(NgoMigratedEvent {
+            ngo: ngo_pubkey,
+            total_received: entry.total_received,
+            is_active: ngo_account.is_active,
+            merged,
+            retired,
+            remaining,
+            timestamp,
+        });
+
+        if remaining == 0 {
+            ctx.accounts
+                .ngo_registry
+                .close(ctx.accounts.operator.to_account_info())?;
+        }
+
+        msg!("NGO migrated: {} ({} left in legacy registry)", ngo_pubkey, remaining);
+        Ok(())
+    }
+
+    /// Move one sponsor from the legacy `SponsorRegistry` into its own account
+    ///
+    /// Only a registry manager can migrate. The entry is carried over
+    /// unchanged, so no proposal is needed. If the sponsor has since
+    /// opened a ledger or been registered again, the legacy totals and
+    /// deposit count are added to that account (an anonymous ledger also
+    /// takes over the legacy name and verification). The legacy registry
+    /// is closed once its last entry has moved.
+    pub fn migrate_sponsor(ctx: Context<MigrateSponsor>, sponsor_pubkey: Pubkey) -> Result<()> {
+        let sponsor_registry = &mut ctx.accounts.sponsor_registry;
+        let index = sponsor_registry
+            .sponsors
+            .iter()
+            .position(|s| s.pubkey == sponsor_pubkey)
+            .ok_or(EscrowError::SponsorNotFound)?;
+        let entry = sponsor_registry.sponsors.remove(index);
+        let remaining = sponsor_registry.sponsors.len() as u32;
+
+        let sponsor_account = &mut ctx.accounts.sponsor_account;
+        let merged = sponsor_account.wallet != Pubkey::default();
+        if merged {
+            sponsor_account.total_deposited = sponsor_account
+                .total_deposited
+                .checked_add(entry.total_deposited)
+                .ok_or(EscrowError::Overflow)?;
+            sponsor_account.deposit_count = sponsor_account
+                .deposit_count
+                .checked_add(entry.deposit_count)
+                .ok_or(EscrowError::Overflow)?;
+            sponsor_account.last_deposit = sponsor_account.last_deposit.max(entry.last_deposit);
+            // An anonymous ledger takes over the legacy registration
+            if !sponsor_account.is_registered {
+                sponsor_account.name = entry.name;
+                sponsor_account.is_registered = true;
+                sponsor_account.is_verified = entry.is_verified;
+            }
+        } else {
+            sponsor_account.wallet = entry.pubkey;
+            sponsor_account.name = entry.name;
+            sponsor_account.total_deposited = entry.total_deposited;
+            sponsor_account.deposit_count = entry.deposit_count;
+            sponsor_account.last_deposit = entry.last_deposit;
+            sponsor_account.is_registered = true;
+            sponsor_account.is_verified = entry.is_verified;
+            sponsor_account.status_reason = None;
+            sponsor_account.status_changed_at = 0;
+            // Legacy deposits predate withdrawal shares and are not withdrawable
+            sponsor_account.shares = 0;
+            sponsor_account.total_withdrawn = 0;
+            sponsor_account.bump = ctx.bumps.sponsor_account;
+        }
+
+        emit!(SponsorMigratedEvent {
+            sponsor: sponsor_pubkey,
+            total_deposited: entry.total_deposited,
+            is_verified: sponsor_account.is_verified,
+            merged,
+            remaining,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        if remaining == 0 {
+            ctx.accounts
+                .sponsor_registry
+                .close(ctx.accounts.operator.to_account_info())?;
+        }
+
+        msg!("Sponsor migrated: {} ({} left in legacy registry)", sponsor_pubkey, remaining);
+        Ok(())
+    }
+
+    /// Append entries to the legacy registries, creating them if needed
+    ///
+    /// Only built with the `legacy-fixtures` feature so localnet tests can
+    /// exercise `migrate_ngo` and `migrate_sponsor`; never deploy with it.
+    #[cfg(feature = "legacy-fixtures")]
+    pub fn write_legacy_registries(
+        ctx: Context<WriteLegacyRegistries>,
+        ngos: Vec<NgoEntry>,
+        sponsors: Vec<SponsorEntry>,
+    ) -> Result<()> {
+        let ngo_registry = &mut ctx.accounts.ngo_registry;
+        ngo_registry.ngos.extend(ngos);
+        require!(ngo_registry.ngos.len() <= MAX_NGOS, EscrowError::NgoRegistryFull);
+        ngo_registry.bump = ctx.bumps.ngo_registry;
+
+        let sponsor_registry = &mut ctx.accounts.sponsor_registry;
+        sponsor_registry.sponsors.extend(sponsors);
+        require!(
+            sponsor_registry.sponsors.len() <= MAX_SPONSORS,
+            EscrowError::SponsorRegistryFull
+        );
+        sponsor_registry.bump = ctx.bumps.sponsor_registry;
+
+        msg!(
+            "Legacy registries hold {} NGOs and {} sponsors",
+            ngo_registry.ngos.len(),
+            sponsor_registry.sponsors.len()
+        );
+        Ok(())
+    }
+
+    /// Get the current escrow status (view function via simulation)
+    pub fn get_status(ctx: Context<GetStatus>) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let vault_balance = ctx.accounts.escrow_vault.lamports();
+
+        msg!("=== Ecoscore Escrow Status ===");
+        msg!("Admins: {:?}", config.admins);
+        msg!("Threshold: {}", config.threshold);
+        msg!("Proposals Created: {}", config.proposal_count);
+        msg!("Total Deposited: {} lamports", config.total_deposited);
+        msg!("Total Disbursed: {} lamports", config.total_disbursed);
+        msg!("Total Withdrawn: {} lamports", config.total_withdrawn);
+        msg!("Conversion Rate: {} lamports per 1000 points", config.lamports_per_1000_points);
+        msg!("Price Feed: {:?}", config.price_feed);
+        msg!("Total Points Redeemed: {}", config.total_points_redeemed);
+        msg!("Last Batch Week: {}", config.last_batch_week);
+        msg!("Paused Flags: {:#05b}", config.paused);
+        msg!("Current Vault Balance: {} lamports", vault_balance);
+        msg!("Vault Reserve: {} lamports", config.vault_reserve()?);
+
+        Ok(())
+    }
+}
+
+// =============================================================================
+// HELPER FUNCTIONS
+// =============================================================================
+
+/// Convert points to base units of any asset given its rate per 1000 points
+fn points_to_units(points: u64, units_per_1000_points: u64) -> Result<u64> {
+    points
+        .checked_mul(units_per_1000_points)
+        .ok_or(EscrowError::Overflow.into())
+        .map(|v| v / 1000)
+}
+
+/// Copy `N` little-endian bytes out of an account's data at `offset`
+fn le_bytes<const N: usize>(data: &[u8], offset: usize) -> [u8; N] {
+    let mut bytes = [0u8; N];
+    bytes.copy_from_slice(&data[offset..offset + N]);
+    bytes
+}
+
+/// Pro-rata multiplier in basis points (10000 = 100%) for paying out
+/// `requested` from a vault holding `available`
+fn pro_rata_bps(requested: u64, available: u64) -> Result<u16> {
+    if requested <= available {
+        return Ok(10000);
+    }
+    // Pro-rata: everyone gets proportionally less
+    Ok((available
+        .checked_mul(10000)
+        .ok_or(EscrowError::Overflow)?
+        .checked_div(requested)
+        .ok_or(EscrowError::Overflow)?) as u16)
+}
+
+/// Scale `amounts` down to sum to exactly `available` if they exceed it
+///
+/// Each share is `amount * available / total` floored in u128; the lamports
+/// the flooring leaves over (fewer than one per share) go one each to the
+/// shares with the largest remainders, ties to the earlier allocation.
+/// Returns the shares and how many lamports were handed out that way.
+fn pro_rata_shares(amounts: &[u64], available: u64) -> Result<(Vec<u64>, u64)> {
+    let requested: u128 = amounts.iter().map(|&a| a as u128).sum();
+    if requested <= available as u128 {
+        return Ok((amounts.to_vec(), 0));
+    }
+
+    let mut shares = Vec::with_capacity(amounts.len());
+    let mut remainders = Vec::with_capacity(amounts.len());
+    for (i, &amount) in amounts.iter().enumerate() {
+        let scaled = amount as u128 * available as u128;
+        shares.push((scaled / requested) as u64);
+        remainders.push((scaled % requested, i));
+    }
+
+    let floored: u64 = shares.iter().sum();
+    let dust = available - floored;
+    remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    for &(_, i) in remainders.iter().take(dust as usize) {
+        shares[i] += 1;
+    }
+    Ok((shares, dust))
+}
+
+/// Transfer fee the sender must add so the recipient receives `net`
+///
+/// Zero for SPL Token mints and Token-2022 mints without a transfer fee.
+fn transfer_fee_for_net(mint: &AccountInfo, net: u64, epoch: u64) -> Result<u64> {
+    if net == 0 || mint.owner != &anchor_spl::token_2022::ID {
+        return Ok(0);
+    }
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<MintState>::unpack(&data)?;
+    match state.get_extension::<TransferFeeConfig>() {
+        Ok(fee_config) => fee_config
+            .calculate_inverse_epoch_fee(epoch, net)
+            .ok_or(EscrowError::Overflow.into()),
+        Err(_) => Ok(0),
+    }
+}
+
+/// Transfer fee the mint withholds when the sender sends `gross`
+///
+/// Zero for SPL Token mints and Token-2022 mints without a transfer fee.
+fn transfer_fee_on_gross(mint: &AccountInfo, gross: u64, epoch: u64) -> Result<u64> {
+    if gross == 0 || mint.owner != &anchor_spl::token_2022::ID {
+        return Ok(0);
+    }
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<MintState>::unpack(&data)?;
+    match state.get_extension::<TransferFeeConfig>() {
+        Ok(fee_config) => fee_config
+            .calculate_epoch_fee(epoch, gross)
+            .ok_or(EscrowError::Overflow.into()),
+        Err(_) => Ok(0),
+    }
+}
+
+/// Load an NGO's token account from remaining_accounts and check it holds
+/// `mint` for `wallet`
+fn load_ngo_token_account<'info>(
+    info: &'info AccountInfo<'info>,
+    wallet: &Pubkey,
+    mint: &Pubkey,
+) -> Result<InterfaceAccount<'info, TokenAccount>> {
+    let token_account = InterfaceAccount::<TokenAccount>::try_from(info)?;
+    require_keys_eq!(token_account.owner, *wallet, EscrowError::AccountMismatch);
+    require_keys_eq!(token_account.mint, *mint, EscrowError::AccountMismatch);
+    Ok(token_account)
+}
+
+/// Load an NGO's account from remaining_accounts and check it is the PDA for `wallet`
+fn load_ngo_account<'info>(
+    info: &'info AccountInfo<'info>,
+    wallet: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Account<'info, NgoAccount>> {
+    let ngo_account = Account::<NgoAccount>::try_from(info)?;
+    let expected = Pubkey::create_program_address(
+        &[NGO_SEED, wallet.as_ref(), &[ngo_account.bump]],
+        program_id,
+    )
+    .map_err(|_| EscrowError::NgoNotFound)?;
+    require_keys_eq!(info.key(), expected, EscrowError::NgoNotFound);
+    Ok(ngo_account)
+}
+
+/// Fail with `CampaignTagTooLong` if a deposit's campaign tag is too long
+fn validate_campaign_tag(campaign_tag: &Option<String>) -> Result<()> {
+    if let Some(tag) = campaign_tag {
+        require!(tag.len() <= MAX_CAMPAIGN_TAG_LEN, EscrowError::CampaignTagTooLong);
+    }
+    Ok(())
+}
+
+/// Earmarks and matching campaigns passed to a batch
+type BatchFunding<'info> = (Vec<Account<'info, Earmark>>, Vec<Account<'info, MatchingCampaign>>);
+
+/// Load the `Earmark` and `MatchingCampaign` accounts passed to a batch
+/// (in any order), rejecting duplicates
+fn load_earmarks_and_campaigns<'info>(
+    infos: &'info [AccountInfo<'info>],
+    program_id: &Pubkey,
+) -> Result<BatchFunding<'info>> {
+    let mut earmarks = Vec::new();
+    let mut campaigns = Vec::new();
+    for (i, info) in infos.iter().enumerate() {
+        require!(
+            !infos[..i].iter().any(|other| other.key == info.key),
+            EscrowError::AccountMismatch
+        );
+        let is_campaign = info.try_borrow_data()?.starts_with(MatchingCampaign::DISCRIMINATOR);
+        let expected = if is_campaign {
+            let campaign = Account::<MatchingCampaign>::try_from(info)?;
+            let expected = Pubkey::create_program_address(
+                &[MATCHING_CAMPAIGN_SEED, &campaign.id.to_le_bytes(), &[campaign.bump]],
+                program_id,
+            );
+            campaigns.push(campaign);
+            expected
+        } else {
+            let earmark = Account::<Earmark>::try_from(info)?;
+            let expected = Pubkey::create_program_address(
+                &[EARMARK_SEED, &earmark.id.to_le_bytes(), &[earmark.bump]],
+                program_id,
+            );
+            earmarks.push(earmark);
+            expected
+        };
+        let expected = expected.map_err(|_| EscrowError::AccountMismatch)?;
+        require_keys_eq!(info.key(), expected, EscrowError::AccountMismatch);
+    }
+    Ok((earmarks, campaigns))
+}
+
+/// Take up to `amount` for an NGO from the earmarks it is eligible for,
+/// in order, emitting an `EarmarkDrawnEvent` per draw. Returns the total drawn.
+fn draw_from_earmarks(
+    earmarks: &mut [Account<Earmark>],
+    ngo: &NgoAccount,
+    amount: u64,
+    week_id: u64,
+    timestamp: i64,
+) -> Result<u64> {
+    let mut drawn: u64 = 0;
+    for earmark in earmarks.iter_mut() {
+        if drawn == amount {
+            break;
+        }
+        if earmark.remaining == 0 || !earmark.restriction.allows(ngo) {
+            continue;
+        }
+        let draw = earmark.remaining.min(amount - drawn);
+        earmark.remaining -= draw;
+        drawn += draw;
+
+        emit!(EarmarkDrawnEvent {
+            earmark_id: earmark.id,
+            sponsor: earmark.sponsor,
+            ngo: ngo.wallet,
+            week_id,
+            amount: draw,
+            remaining: earmark.remaining,
+            timestamp,
+        });
+    }
+    Ok(drawn)
+}
+
+/// Add the matches for an NGO's base disbursement from every campaign that
+/// covers it and is running, emitting a `MatchAppliedEvent` per campaign.
+/// Each match is capped by what is left of that campaign. Returns the total.
+fn draw_matches(
+    campaigns: &mut [Account<MatchingCampaign>],
+    ngo: &NgoAccount,
+    base_amount: u64,
+    week_id: u64,
+    timestamp: i64,
+) -> Result<u64> {
+    let mut matched: u64 = 0;
+    for campaign in campaigns.iter_mut() {
+        if !campaign.is_running(timestamp) || !campaign.covers(ngo) {
+            continue;
+        }
+        let full_match: u64 = ((base_amount as u128) * (campaign.match_bps as u128) / 10000)
+            .try_into()
+            .map_err(|_| EscrowError::Overflow)?;
+        let amount = full_match.min(campaign.remaining());
+        if amount == 0 {
+            continue;
+        }
+        campaign.matched += amount;
+        matched = matched.checked_add(amount).ok_or(EscrowError::Overflow)?;
+
+        emit!(MatchAppliedEvent {
+            campaign_id: campaign.id,
+            sponsor: campaign.sponsor,
+            ngo: ngo.wallet,
+            week_id,
+            amount,
+            remaining: campaign.remaining(),
+            timestamp,
+        });
+    }
+    Ok(matched)
+}
+
+/// Add an allocation to its category's running totals for a batch
+fn add_category_total(
+    totals: &mut Vec<CategoryTotal>,
+    category: NgoCategory,
+    points: u64,
+    amount: u64,
+) -> Result<()> {
+    let index = match totals.iter().position(|t| t.category == category) {
+        Some(index) => index,
+        None => {
+            totals.push(CategoryTotal {
+                category,
+                points_pledged: 0,
+                amount_disbursed: 0,
+                max_share_bps: 10000,
+            });
+            totals.len() - 1
+        }
+    };
+    let total = &mut totals[index];
+    total.points_pledged = total
+        .points_pledged
+        .checked_add(points)
+        .ok_or(EscrowError::Overflow)?;
+    total.amount_disbursed = total
+        .amount_disbursed
+        .checked_add(amount)
+        .ok_or(EscrowError::Overflow)?;
+    Ok(())
+}
+
+/// Validate a new admin set and threshold
+fn validate_admin_set(admins: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(!admins.is_empty(), EscrowError::InvalidThreshold);
+    require!(admins.len() <= MAX_ADMINS, EscrowError::TooManyAdmins);
+    require!(
+        threshold > 0 && threshold as usize <= admins.len(),
+        EscrowError::InvalidThreshold
+    );
+    for (i, admin) in admins.iter().enumerate() {
+        require!(!admins[..i].contains(admin), EscrowError::DuplicateAdmin);
+    }
+    Ok(())
+}
+
+// =============================================================================
+// ACCOUNT STRUCTURES
+// =============================================================================
+
+/// Configuration account storing the admin set and totals
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    /// Admin signers who create and approve proposals (M-of-N)
+    #[max_len(MAX_ADMINS)]
+    pub admins: Vec<Pubkey>,
+    /// Number of admin approvals required to execute a proposal
+    pub threshold: u8,
+    /// Number of proposals created (next proposal id)
+    pub proposal_count: u64,
+    /// Admin handover awaiting acceptance by the new key
+    pub pending_admin: Option<PendingAdmin>,
+    /// Number of completed admin handovers (next history record id)
+    pub admin_change_count: u64,
+    /// Seconds a timelocked proposal must wait after reaching threshold
+    pub timelock_delay: i64,
+    /// Single disbursements above this many lamports are timelocked
+    pub timelock_disburse_threshold: u64,
+    /// Paused instruction classes (bitmask of `PAUSE_*` flags)
+    pub paused: u8,
+    /// Maximum share of a batch's points per `NgoCategory`, in basis points
+    pub category_caps_bps: [u16; NGO_CATEGORY_COUNT],
+    /// Total lamports deposited by all sponsors
+    pub total_deposited: u64,
+    /// Total lamports disbursed to all NGOs
+    pub total_disbursed: u64,
+    /// Total points redeemed by users across all batches
+    pub total_points_redeemed: u64,
+    /// Last processed batch week (YYYYWW format, e.g., 202605)
+    pub last_batch_week: u64,
+    /// Number of earmarked deposits made (next earmark id)
+    pub earmark_count: u64,
+    /// Lamports in the vault still reserved by earmarks
+    pub earmarked_balance: u64,
+    /// Number of matching campaigns created (next campaign id)
+    pub campaign_count: u64,
+    /// Lamports in the vault still reserved by matching campaigns
+    pub matching_balance: u64,
+    /// Number of NGO ids issued (next NGO id)
+    pub ngo_count: u64,
+    /// Seconds a sponsor withdrawal must wait after being requested
+    pub withdrawal_notice: i64,
+    /// Withdrawal shares held across all sponsor ledgers
+    pub sponsor_shares: u128,
+    /// Unrestricted lamports those shares are worth (sponsor deposits less
+    /// their pro-rata share of disbursements and less withdrawals)
+    pub attributed_balance: u64,
+    /// Total lamports withdrawn by sponsors
+    pub total_withdrawn: u64,
+    /// Lamports paid per 1000 pledged points when no price feed is set
+    pub lamports_per_1000_points: u64,
+    /// Pyth-layout SOL/USD price account batches are priced from, if any
+    pub price_feed: Option<Pubkey>,
+    /// Oldest feed price, in seconds, a batch will pay out against
+    pub max_price_age: i64,
+    /// Widest feed confidence interval a batch accepts, in basis points
+    /// of the price
+    pub max_price_confidence_bps: u16,
+    /// Fewest points one NGO may be allocated in a batch
+    pub min_pledge_points: u64,
+    /// Most points one NGO may be allocated in a batch (0 = no maximum)
+    pub max_pledge_points: u64,
+    /// Skip allocations under the minimum instead of failing the batch
+    pub roll_over_small_pledges: bool,
+    /// Lamports kept in the vault on top of its rent-exempt minimum
+    pub vault_buffer: u64,
+    /// Bump seed for this PDA
+    pub bump: u8,
+    /// Bump seed for the vault PDA
+    pub vault_bump: u8,
+}
+
+impl Config {
+    pub fn is_admin(&self, key: &Pubkey) -> bool {
+        self.admins.contains(key)
+    }
+
+    /// Fail with `Paused` if any of `flags` is currently paused
+    pub fn require_not_paused(&self, flags: u8) -> Result<()> {
+        require!(self.paused & flags == 0, EscrowError::Paused);
+        Ok(())
+    }
+
+    /// Take the next NGO id; ids are never reissued
+    pub fn issue_ngo_id(&mut self) -> Result<u64> {
+        let id = self.ngo_count;
+        self.ngo_count = id.checked_add(1).ok_or(EscrowError::Overflow)?;
+        Ok(id)
+    }
+
+    /// Vault lamports set aside for earmarks and matching campaigns, which
+    /// only those can spend
+    pub fn reserved_balance(&self) -> Result<u64> {
+        self.earmarked_balance
+            .checked_add(self.matching_balance)
+            .ok_or(EscrowError::Overflow.into())
+    }
+
+    /// Lamports that `shares` withdrawal shares are currently worth
+    pub fn shares_value(&self, shares: u128) -> Result<u64> {
+        if self.sponsor_shares == 0 {
+            return Ok(0);
+        }
+        let value = shares
+            .checked_mul(self.attributed_balance as u128)
+            .ok_or(EscrowError::Overflow)?
+            / self.sponsor_shares;
+        Ok(value.try_into().map_err(|_| EscrowError::Overflow)?)
+    }
+
+    /// Shares needed to withdraw `amount` lamports (rounded up)
+    pub fn shares_for(&self, amount: u64) -> Result<u128> {
+        let numerator = (amount as u128)
+            .checked_mul(self.sponsor_shares)
+            .ok_or(EscrowError::Overflow)?;
+        Ok(numerator.div_ceil((self.attributed_balance as u128).max(1)))
+    }
+
+    /// Attribute a ledger deposit of `amount` unrestricted lamports to its
+    /// sponsor, returning the withdrawal shares issued
+    pub fn issue_shares(&mut self, amount: u64) -> Result<u128> {
+        let shares = if self.sponsor_shares == 0 {
+            amount as u128
+        } else {
+            (amount as u128)
+                .checked_mul(self.sponsor_shares)
+                .ok_or(EscrowError::Overflow)?
+                / (self.attributed_balance as u128).max(1)
+        };
+        self.sponsor_shares = self.sponsor_shares.checked_add(shares).ok_or(EscrowError::Overflow)?;
+        self.attributed_balance = self
+            .attributed_balance
+            .checked_add(amount)
+            .ok_or(EscrowError::Overflow)?;
+        Ok(shares)
+    }
+
+    /// Remove `shares` worth `value` lamports from the attributed pool
+    /// (on withdrawal, or when a sponsor's ledger is archived)
+    pub fn redeem_shares(&mut self, shares: u128, value: u64) -> Result<()> {
+        self.sponsor_shares = self.sponsor_shares.checked_sub(shares).ok_or(EscrowError::Overflow)?;
+        self.attributed_balance = self
+            .attributed_balance
+            .checked_sub(value)
+            .ok_or(EscrowError::Overflow)?;
+        Ok(())
+    }
+
+    /// Vault lamports never paid out: the vault's rent-exempt minimum plus
+    /// `vault_buffer`
+    pub fn vault_reserve(&self) -> Result<u64> {
+        Rent::get()?
+            .minimum_balance(0)
+            .checked_add(self.vault_buffer)
+            .ok_or(EscrowError::Overflow.into())
+    }
+
+    /// Lamports of a vault holding `vault_balance` that unrestricted payouts
+    /// may use: everything above the earmark and campaign reservations and
+    /// the vault reserve
+    pub fn unrestricted_balance(&self, vault_balance: u64) -> Result<u64> {
+        let held = self
+            .reserved_balance()?
+            .checked_add(self.vault_reserve()?)
+            .ok_or(EscrowError::Overflow)?;
+        Ok(vault_balance.saturating_sub(held))
+    }
+
+    /// Charge sponsors their pro-rata share of `amount` unrestricted
+    /// lamports paid out of an unrestricted pool of `unrestricted`
+    pub fn record_unrestricted_disbursement(&mut self, amount: u64, unrestricted: u64) -> Result<()> {
+        if unrestricted == 0 {
+            return Ok(());
+        }
+        let kept = unrestricted.checked_sub(amount).ok_or(EscrowError::InsufficientFunds)?;
+        self.attributed_balance =
+            ((self.attributed_balance as u128) * (kept as u128) / (unrestricted as u128)) as u64;
+        Ok(())
+    }
+
+    /// Split a batch's allocations into those to pay, with their index in
+    /// the batch, and those under the minimum to roll over
+    ///
+    /// Fails with `PledgeAboveMaximum` for any allocation over the maximum
+    /// and with `PledgeBelowMinimum` for one under the minimum unless
+    /// rollover is on.
+    pub fn split_by_pledge_limits(&self, allocations: &[BatchAllocation]) -> Result<PledgeSplit> {
+        let mut payable = Vec::with_capacity(allocations.len());
+        let mut rolled_over = Vec::new();
+        for (i, allocation) in allocations.iter().enumerate() {
+            require!(
+                self.max_pledge_points == 0 || allocation.points_pledged <= self.max_pledge_points,
+                EscrowError::PledgeAboveMaximum
+            );
+            if allocation.points_pledged >= self.min_pledge_points {
+                payable.push((i, allocation.clone()));
+            } else {
+                require!(self.roll_over_small_pledges, EscrowError::PledgeBelowMinimum);
+                rolled_over.push(allocation.clone());
+            }
+        }
+        Ok((payable, rolled_over))
+    }
+
+    /// Fail with `CategoryCapExceeded` if any category's share of
+    /// `total_points` is above its configured cap, recording the cap
+    /// applied to each category for the batch event
+    pub fn require_within_category_caps(&self, totals: &mut [CategoryTotal], total_points: u64) -> Result<()> {
+        for total in totals.iter_mut() {
+            total.max_share_bps = self.category_caps_bps[total.category.index()];
+            require!(
+                (total.points_pledged as u128) * 10000
+                    <= (total.max_share_bps as u128) * (total_points as u128),
+                EscrowError::CategoryCapExceeded
+            );
+        }
+        Ok(())
+    }
+}
+
+/// An admin seat handover awaiting acceptance
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct PendingAdmin {
+    /// Admin being replaced
+    pub old_admin: Pubkey,
+    /// Key that must sign `accept_admin`
+    pub new_admin: Pubkey,
+    /// When the handover was proposed
+    pub proposed_at: i64,
+}
+
+/// Permanent on-chain record of a completed admin handover
+#[account]
+#[derive(InitSpace)]
+pub struct AdminChangeRecord {
+    /// Sequential id (also the PDA seed)
+    pub change_id: u64,
+    /// Admin that was replaced
+    pub old_admin: Pubkey,
+    /// Admin that took over the seat
+    pub new_admin: Pubkey,
+    /// When the handover was proposed
+    pub proposed_at: i64,
+    /// When the new admin accepted
+    pub accepted_at: i64,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+/// Operational roles that can be granted independently of admin status
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum Role {
+    /// Curates the NGO whitelist and sponsor registry
+    RegistryManager,
+    /// Executes single and batch disbursements
+    Disburser,
+    /// Trips the emergency circuit breaker
+    Pauser,
+    /// Can veto (cancel) pending proposals
+    Auditor,
+}
+
+impl Role {
+    pub fn bit(self) -> u8 {
+        match self {
+            Role::RegistryManager => 1 << 0,
+            Role::Disburser => 1 << 1,
+            Role::Pauser => 1 << 2,
+            Role::Auditor => 1 << 3,
+        }
+    }
+}
+
+/// Roles held by a single key
+#[account]
+#[derive(InitSpace)]
+pub struct RoleAssignment {
+    /// The key holding these roles
+    pub holder: Pubkey,
+    /// Bitmask of `Role::bit()` values
+    pub roles: u8,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl RoleAssignment {
+    pub fn has(&self, role: Role) -> bool {
+        self.roles & role.bit() != 0
+    }
+}
+
+/// Privileged action awaiting multisig approval
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum ProposalAction {
+    AddNgo {
+        ngo: Pubkey,
+        #[max_len(64)]
+        name: String,
+    },
+    RemoveNgo {
+        ngo: Pubkey,
+        reason: StatusReason,
+    },
+    ReactivateNgo {
+        ngo: Pubkey,
+        reason: StatusReason,
+    },
+    UpdateNgoProfile {
+        ngo: Pubkey,
+        profile: NgoProfile,
+    },
+    RotateNgoWallet {
+        old_wallet: Pubkey,
+        new_wallet: Pubkey,
+    },
+    ApproveNgoApplication {
+        ngo: Pubkey,
+    },
+    ArchiveNgo {
+        ngo: Pubkey,
+    },
+    RenewNgoVerification {
+        ngo: Pubkey,
+        verified_until: i64,
+    },
+    RegisterSponsor {
+        sponsor: Pubkey,
+        #[max_len(64)]
+        name: String,
+    },
+    RemoveSponsor {
+        sponsor: Pubkey,
+        reason: StatusReason,
+    },
+    ReverifySponsor {
+        sponsor: Pubkey,
+        reason: StatusReason,
+    },
+    ArchiveSponsor {
+        sponsor: Pubkey,
+    },
+    RefundEarmark {
+        earmark_id: u64,
+    },
+    Disburse {
+        ngo: Pubkey,
+        amount: u64,
+    },
+    BatchDisburse {
+        week_id: u64,
+        #[max_len(MAX_BATCH_SIZE)]
+        allocations: Vec<BatchAllocation>,
+    },
+    AddMint {
+        mint: Pubkey,
+        units_per_1000_points: u64,
+    },
+    DisburseToken {
+        mint: Pubkey,
+        ngo: Pubkey,
+        amount: u64,
+    },
+    BatchDisburseToken {
+        mint: Pubkey,
+        week_id: u64,
+        #[max_len(MAX_BATCH_SIZE)]
+        allocations: Vec<BatchAllocation>,
+    },
+    SetAdmins {
+        #[max_len(MAX_ADMINS)]
+        admins: Vec<Pubkey>,
+        threshold: u8,
+    },
+    ProposeAdmin {
+        old_admin: Pubkey,
+        new_admin: Pubkey,
+    },
+    GrantRole {
+        holder: Pubkey,
+        role: Role,
+    },
+    RevokeRole {
+        holder: Pubkey,
+        role: Role,
+    },
+    SetTimelock {
+        delay: i64,
+        disburse_threshold: u64,
+    },
+    SetCategoryCap {
+        category: NgoCategory,
+        max_share_bps: u16,
+    },
+    SetWithdrawalNotice {
+        notice: i64,
+    },
+    SetConversionRate {
+        lamports_per_1000_points: u64,
+    },
+    SetPriceFeed {
+        price_feed: Option<Pubkey>,
+        max_age: i64,
+        max_confidence_bps: u16,
+    },
+    SetPledgeLimits {
+        min_points: u64,
+        max_points: u64,
+        roll_over: bool,
+    },
+    SetVaultBuffer {
+        buffer: u64,
+    },
+    Unpause {
+        flags: u8,
+    },
+}
+
+impl ProposalAction {
+    /// Reject actions that could never be executed
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            ProposalAction::AddNgo { name, .. } | ProposalAction::RegisterSponsor { name, .. } => {
+                require!(name.len() <= 64, EscrowError::NameTooLong);
+            }
+            ProposalAction::UpdateNgoProfile { profile, .. } => {
+                profile.validate()?;
+            }
+            ProposalAction::Disburse { amount, .. } | ProposalAction::DisburseToken { amount, .. } => {
+                require!(*amount > 0, EscrowError::InvalidAmount);
+            }
+            ProposalAction::AddMint {
+                units_per_1000_points,
+                ..
+            } => {
+                require!(*units_per_1000_points > 0, EscrowError::InvalidAmount);
+            }
+            ProposalAction::SetConversionRate {
+                lamports_per_1000_points,
+            } => {
+                require!(*lamports_per_1000_points > 0, EscrowError::InvalidAmount);
+            }
+            ProposalAction::BatchDisburse { allocations, .. }
+            | ProposalAction::BatchDisburseToken { allocations, .. } => {
+                require!(
+                    allocations.len() <= MAX_BATCH_SIZE,
+                    EscrowError::BatchTooLarge
+                );
+                // Each NGO account is loaded and written back once per
+                // allocation, so a repeated NGO would undercount its totals
+                for (i, allocation) in allocations.iter().enumerate() {
+                    require!(
+                        !allocations[..i].iter().any(|a| a.ngo == allocation.ngo),
+                        EscrowError::DuplicateNgo
+                    );
+                }
+            }
+            ProposalAction::SetAdmins { admins, threshold } => {
+                validate_admin_set(admins, *threshold)?;
+            }
+            ProposalAction::ProposeAdmin {
+                old_admin,
+                new_admin,
+            } => {
+                require!(old_admin != new_admin, EscrowError::DuplicateAdmin);
+            }
+            ProposalAction::RotateNgoWallet {
+                old_wallet,
+                new_wallet,
+            } => {
+                require_keys_neq!(*old_wallet, *new_wallet, EscrowError::SameWallet);
+            }
+            ProposalAction::SetCategoryCap { max_share_bps, .. } => {
+                require!(*max_share_bps <= 10000, EscrowError::InvalidCategoryCap);
+            }
+            ProposalAction::SetTimelock { delay, .. } => {
+                require!(
+                    (0..=MAX_TIMELOCK_DELAY).contains(delay),
+                    EscrowError::InvalidTimelockDelay
+                );
+            }
+            ProposalAction::SetWithdrawalNotice { notice } => {
+                require!(
+                    (0..=MAX_WITHDRAWAL_NOTICE).contains(notice),
+                    EscrowError::InvalidWithdrawalNotice
+                );
+            }
+            ProposalAction::SetPledgeLimits {
+                min_points,
+                max_points,
+                ..
+            } => {
+                require!(
+                    *max_points == 0 || max_points >= min_points,
+                    EscrowError::InvalidPledgeLimits
+                );
+            }
+            ProposalAction::SetPriceFeed {
+                price_feed,
+                max_age,
+                max_confidence_bps,
+            } => {
+                if let Some(feed) = price_feed {
+                    // A single admin can write the mock feed, so only
+                    // `mock-oracle` test builds may price batches from it
+                    if cfg!(not(feature = "mock-oracle")) {
+                        let (mock_feed, _) = Pubkey::find_program_address(&[MOCK_PRICE_FEED_SEED], &crate::ID);
+                        require_keys_neq!(*feed, mock_feed, EscrowError::InvalidPriceFeed);
+                    }
+                    require!(
+                        (1..=MAX_PRICE_AGE).contains(max_age)
+                            && (1..=10000).contains(max_confidence_bps),
+                        EscrowError::InvalidPriceFeedSettings
+                    );
+                }
+            }
+            ProposalAction::RemoveNgo { .. }
+            | ProposalAction::ReactivateNgo { .. }
+            | ProposalAction::RemoveSponsor { .. }
+            | ProposalAction::ReverifySponsor { .. }
+            | ProposalAction::ApproveNgoApplication { .. }
+            | ProposalAction::RenewNgoVerification { .. }
+            | ProposalAction::ArchiveNgo { .. }
+            | ProposalAction::ArchiveSponsor { .. }
+            | ProposalAction::RefundEarmark { .. }
+            | ProposalAction::GrantRole { .. }
+            | ProposalAction::RevokeRole { .. }
+            | ProposalAction::SetVaultBuffer { .. }
+            | ProposalAction::Unpause { .. } => {}
+        }
+        Ok(())
+    }
+
+    /// Whether this action must wait out `config.timelock_delay`
+    ///
+    /// Whitelisting recipients or mints, changing who governs the program,
+    /// large single disbursements and all single token disbursements (whose
+    /// amounts are in each mint's own units) are delayed so watchers can
+    /// react before they take effect.
+    pub fn requires_timelock(&self, config: &Config) -> bool {
+        match self {
+            ProposalAction::AddNgo { .. }
+            | ProposalAction::AddMint { .. }
+            | ProposalAction::DisburseToken { .. }
+            | ProposalAction::ReactivateNgo { .. }
+            | ProposalAction::RotateNgoWallet { .. }
+            | ProposalAction::ApproveNgoApplication { .. }
+            | ProposalAction::SetAdmins { .. }
+            | ProposalAction::ProposeAdmin { .. }
+            | ProposalAction::SetTimelock { .. }
+            | ProposalAction::SetCategoryCap { .. }
+            | ProposalAction::SetWithdrawalNotice { .. }
+            | ProposalAction::SetConversionRate { .. }
+            | ProposalAction::SetPriceFeed { .. }
+            | ProposalAction::SetPledgeLimits { .. }
+            | ProposalAction::SetVaultBuffer { .. } => true,
+            ProposalAction::Disburse { amount, .. } => *amount > config.timelock_disburse_threshold,
+            _ => false,
+        }
+    }
+}
+
+/// Lifecycle of a proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ProposalStatus {
+    Pending,
+    Executed,
+    Cancelled,
+}
+
+/// A privileged action and the admins who approved it
+#[account]
 #[derive(InitSpace)]
-pub struct Config {
-    /// The admin who can add NGOs and trigger disbursements
+pub struct Proposal {
+    /// Sequential id (also the PDA seed)
+    pub id: u64,
+    /// Admin who created the proposal
+    pub proposer: Pubkey,
+    /// The action this proposal authorizes
+    pub action: ProposalAction,
+    /// Admins who approved (the proposer is included)
+    #[max_len(MAX_ADMINS)]
+    pub approvals: Vec<Pubkey>,
+    /// Current status
+    pub status: ProposalStatus,
+    /// Creation timestamp
+    pub created_at: i64,
+    /// Earliest execution time, set once the threshold is reached
+    pub eta: Option<i64>,
+    /// When the threshold was reached; the timelock runs from here
+    pub queued_at: Option<i64>,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl Proposal {
+    /// Approvals from keys that are still in the admin set
+    pub fn approval_count(&self, config: &Config) -> usize {
+        self.approvals.iter().filter(|a| config.is_admin(a)).count()
+    }
+
+    /// Queue the proposal once it first reaches the approval threshold
+    ///
+    /// Sets `eta`, adding the timelock delay for sensitive actions.
+    pub fn queue_if_approved(&mut self, config: &Config, now: i64) -> Result<()> {
+        if self.eta.is_some() || self.approval_count(config) < config.threshold as usize {
+            return Ok(());
+        }
+
+        let timelocked = self.action.requires_timelock(config);
+        let delay = if timelocked { config.timelock_delay } else { 0 };
+        let eta = now.checked_add(delay).ok_or(EscrowError::Overflow)?;
+        self.eta = Some(eta);
+        self.queued_at = Some(now);
+
+        emit!(ProposalQueuedEvent {
+            proposal_id: self.id,
+            action: self.action.clone(),
+            timelocked,
+            eta,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Consume this proposal to authorize `action`
+    ///
+    /// Fails unless the proposal is pending, matches `action` exactly,
+    /// has reached the approval threshold and, for timelocked actions,
+    /// has waited out its delay.
+    ///
+    /// The delay and disburse threshold may have changed since queueing,
+    /// so the timelock is re-evaluated against the current config and
+    /// the queued `eta` is only what was announced.
+    pub fn execute(&mut self, config: &Config, action: &ProposalAction, executor: Pubkey) -> Result<()> {
+        require!(
+            self.status == ProposalStatus::Pending,
+            EscrowError::ProposalNotPending
+        );
+        require!(self.action == *action, EscrowError::ProposalMismatch);
+        require!(
+            self.approval_count(config) >= config.threshold as usize,
+            EscrowError::ThresholdNotMet
+        );
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        if self.action.requires_timelock(config) {
+            let queued_at = self.queued_at.ok_or(EscrowError::ProposalNotQueued)?;
+            let eta = queued_at
+                .checked_add(config.timelock_delay)
+                .ok_or(EscrowError::Overflow)?;
+            require!(timestamp >= eta, EscrowError::TimelockNotExpired);
+        }
+
+        self.status = ProposalStatus::Executed;
+
+        emit!(ProposalExecutedEvent {
+            proposal_id: self.id,
+            executor,
+            timestamp,
+        });
+        Ok(())
+    }
+}
+
+/// A pending request from an NGO wallet to be whitelisted
+#[account]
+#[derive(InitSpace)]
+pub struct NgoApplication {
+    /// The applying NGO's wallet (also the PDA seed)
+    pub wallet: Pubkey,
+    /// Profile the NGO will be whitelisted with if approved
+    pub profile: NgoProfile,
+    /// When the application was submitted
+    pub applied_at: i64,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+/// Reason code recorded when an NGO or sponsor changes status
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum StatusReason {
+    /// Registration or tax paperwork has lapsed
+    ExpiredPaperwork,
+    /// An audit is in progress
+    AuditInProgress,
+    /// Compliance or fraud concern under review
+    ComplianceConcern,
+    /// The organization asked to be paused or resumed
+    RequestedByOrganization,
+    /// The issue behind an earlier suspension was resolved
+    IssueResolved,
+    /// Anything else (details kept off-chain)
+    Other,
+}
+
+/// Cause category shown on the NGO's profile
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum NgoCategory {
+    Unspecified,
+    Climate,
+    Conservation,
+    Oceans,
+    Forests,
+    Wildlife,
+    CleanEnergy,
+    WasteReduction,
+    Water,
+    Agriculture,
+    Education,
+    Other,
+}
+
+impl NgoCategory {
+    /// Position of this category in `Config.category_caps_bps`
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Editable public profile of an NGO
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace, Debug)]
+pub struct NgoProfile {
+    /// Human-readable name (max 64 chars)
+    #[max_len(64)]
+    pub name: String,
+    /// Public website URL (max 128 chars)
+    #[max_len(MAX_WEBSITE_LEN)]
+    pub website: String,
+    /// Off-chain metadata document (max 128 chars)
+    #[max_len(MAX_METADATA_URI_LEN)]
+    pub metadata_uri: String,
+    /// SHA-256 of the metadata document
+    pub content_hash: [u8; 32],
+    /// Cause category
+    pub category: NgoCategory,
+    /// ISO 3166-1 alpha-2 country code, or zeroed when unknown
+    pub country_code: [u8; 2],
+}
+
+impl NgoProfile {
+    /// A profile with only a name set (used when whitelisting)
+    pub fn named(name: String) -> Self {
+        NgoProfile {
+            name,
+            website: String::new(),
+            metadata_uri: String::new(),
+            content_hash: [0; 32],
+            category: NgoCategory::Unspecified,
+            country_code: [0; 2],
+        }
+    }
+
+    /// Check field sizes and the country code format
+    pub fn validate(&self) -> Result<()> {
+        require!(self.name.len() <= 64, EscrowError::NameTooLong);
+        require!(
+            self.website.len() <= MAX_WEBSITE_LEN,
+            EscrowError::WebsiteTooLong
+        );
+        require!(
+            self.metadata_uri.len() <= MAX_METADATA_URI_LEN,
+            EscrowError::MetadataUriTooLong
+        );
+        require!(
+            self.country_code == [0; 2] || self.country_code.iter().all(u8::is_ascii_uppercase),
+            EscrowError::InvalidCountryCode
+        );
+        Ok(())
+    }
+}
+
+/// A whitelisted NGO (one PDA per NGO wallet)
+#[account]
+#[derive(InitSpace)]
+pub struct NgoAccount {
+    /// Sequential NGO id (never reissued; kept across wallet rotations)
+    pub id: u64,
+    /// The NGO's current payout wallet (also the PDA seed)
+    pub wallet: Pubkey,
+    /// Wallet the NGO was first whitelisted with (stable identity)
+    pub original_wallet: Pubkey,
+    /// Number of wallet rotations
+    pub rotation_count: u32,
+    /// Human-readable name (max 64 chars)
+    #[max_len(64)]
+    pub name: String,
+    /// Public website URL
+    #[max_len(MAX_WEBSITE_LEN)]
+    pub website: String,
+    /// Off-chain metadata document (e.g. `ipfs://<CID>`)
+    #[max_len(MAX_METADATA_URI_LEN)]
+    pub metadata_uri: String,
+    /// SHA-256 of the metadata document, so copies can be verified
+    pub content_hash: [u8; 32],
+    /// Cause category
+    pub category: NgoCategory,
+    /// ISO 3166-1 alpha-2 country code (zeroed when unset)
+    pub country_code: [u8; 2],
+    /// Total lamports received by this NGO
+    pub total_received: u64,
+    /// Number of disbursements received (single and batch)
+    pub disbursement_count: u64,
+    /// Timestamp of the last disbursement
+    pub last_disbursement: i64,
+    /// When the NGO was whitelisted (or migrated from the legacy registry)
+    pub added_at: i64,
+    /// Disbursements are refused after this time until the NGO is re-verified
+    pub verified_until: i64,
+    /// Whether this NGO can receive disbursements
+    pub is_active: bool,
+    /// Reason for the last deactivation or reactivation
+    pub status_reason: Option<StatusReason>,
+    /// When `is_active` last changed (or the account was created)
+    pub status_changed_at: i64,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl NgoAccount {
+    /// Fill a freshly created account for a newly whitelisted NGO
+    pub fn whitelist(&mut self, id: u64, wallet: Pubkey, profile: NgoProfile, timestamp: i64, bump: u8) {
+        self.id = id;
+        self.wallet = wallet;
+        self.original_wallet = wallet;
+        self.rotation_count = 0;
+        self.set_profile(profile);
+        self.total_received = 0;
+        self.disbursement_count = 0;
+        self.last_disbursement = 0;
+        self.added_at = timestamp;
+        self.verified_until = timestamp.saturating_add(VERIFICATION_PERIOD);
+        self.is_active = true;
+        self.status_reason = None;
+        self.status_changed_at = timestamp;
+        self.bump = bump;
+    }
+
+    pub fn profile(&self) -> NgoProfile {
+        NgoProfile {
+            name: self.name.clone(),
+            website: self.website.clone(),
+            metadata_uri: self.metadata_uri.clone(),
+            content_hash: self.content_hash,
+            category: self.category,
+            country_code: self.country_code,
+        }
+    }
+
+    pub fn set_profile(&mut self, profile: NgoProfile) {
+        self.name = profile.name;
+        self.website = profile.website;
+        self.metadata_uri = profile.metadata_uri;
+        self.content_hash = profile.content_hash;
+        self.category = profile.category;
+        self.country_code = profile.country_code;
+    }
+
+    /// Fail unless this NGO can receive funds at `now`
+    pub fn require_eligible(&self, now: i64) -> Result<()> {
+        require!(self.is_active, EscrowError::NgoNotActive);
+        require!(now < self.verified_until, EscrowError::NgoVerificationExpired);
+        Ok(())
+    }
+
+    /// Add a disbursement to this NGO's running stats
+    pub fn record_disbursement(&mut self, amount: u64, timestamp: i64) -> Result<()> {
+        self.total_received = self
+            .total_received
+            .checked_add(amount)
+            .ok_or(EscrowError::Overflow)?;
+        self.disbursement_count = self
+            .disbursement_count
+            .checked_add(1)
+            .ok_or(EscrowError::Overflow)?;
+        self.last_disbursement = timestamp;
+        Ok(())
+    }
+}
+
+/// Marker left for a wallet an NGO rotated away from or was archived under
+///
+/// `migrate_ngo` brings such wallets in inactive, so putting one back into
+/// service takes a `ReactivateNgo` proposal.
+#[account]
+#[derive(InitSpace)]
+pub struct RetiredNgoWallet {
+    /// The retired wallet (also the PDA seed)
+    pub wallet: Pubkey,
+    /// When the wallet was last retired
+    pub retired_at: i64,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+/// Entry for a single NGO in the legacy registry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct NgoEntry {
+    /// The NGO's wallet address
+    pub pubkey: Pubkey,
+    /// Human-readable name (max 64 chars)
+    #[max_len(64)]
+    pub name: String,
+    /// Total lamports received by this NGO
+    pub total_received: u64,
+    /// Whether this NGO can receive disbursements
+    pub is_active: bool,
+}
+
+/// Config layout from before the multisig upgrade
+///
+/// Stored under the same seed and discriminator as `Config`;
+/// `migrate_config` reads it and rewrites the account in the current layout.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacyConfig {
+    /// The single admin
     pub admin: Pubkey,
     /// Total lamports deposited by all sponsors
     pub total_deposited: u64,
-    /// Total lamports disbursed to all NGOs
-    pub total_disbursed: u64,
-    /// Total points redeemed by users across all batches
-    pub total_points_redeemed: u64,
-    /// Last processed batch week (YYYYWW format, e.g., 202605)
-    pub last_batch_week: u64,
+    /// Total lamports disbursed to all NGOs
+    pub total_disbursed: u64,
+    /// Total points redeemed by users across all batches
+    pub total_points_redeemed: u64,
+    /// Last processed batch week (YYYYWW format)
+    pub last_batch_week: u64,
+    /// Bump seed for the config PDA
+    pub bump: u8,
+    /// Bump seed for the vault PDA
+    pub vault_bump: u8,
+}
+
+/// Legacy registry of whitelisted NGOs, replaced by `NgoAccount`
+///
+/// No longer created by `initialize`; existing deployments drain it
+/// with `migrate_ngo`.
+#[account]
+pub struct NgoRegistry {
+    /// List of all registered NGOs
+    pub ngos: Vec<NgoEntry>,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl NgoRegistry {
+    pub const SPACE: usize = 8  // discriminator
+        + 4  // Vec length prefix
+        + (MAX_NGOS * (32 + 4 + 64 + 8 + 1))  // MAX_NGOS * NgoEntry size
+        + 1; // bump
+}
+
+/// Deposit ledger for a single sponsor (one PDA per sponsor wallet)
+///
+/// Either a registered brand partner or an anonymous ledger opened by
+/// the depositor with `open_sponsor_ledger`.
+#[account]
+#[derive(InitSpace)]
+pub struct SponsorAccount {
+    /// The sponsor's wallet address (also the PDA seed)
+    pub wallet: Pubkey,
+    /// Human-readable brand name (max 64 chars, empty when anonymous)
+    #[max_len(64)]
+    pub name: String,
+    /// Total lamports deposited by this sponsor
+    pub total_deposited: u64,
+    /// Number of deposits made
+    pub deposit_count: u32,
+    /// Timestamp of last deposit
+    pub last_deposit: i64,
+    /// Whether this sponsor was registered through a proposal
+    pub is_registered: bool,
+    /// Whether this sponsor is a verified partner
+    pub is_verified: bool,
+    /// Reason for the last unverification or re-verification
+    pub status_reason: Option<StatusReason>,
+    /// When `is_verified` last changed (0 if never)
+    pub status_changed_at: i64,
+    /// Withdrawal shares of the unrestricted pool held by this sponsor
+    pub shares: u128,
+    /// Total lamports withdrawn by this sponsor
+    pub total_withdrawn: u64,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl SponsorAccount {
+    /// Add a lamport deposit to this sponsor's running totals, returning
+    /// its sequence number
+    pub fn record_deposit(&mut self, amount: u64, timestamp: i64) -> Result<u32> {
+        self.total_deposited = self
+            .total_deposited
+            .checked_add(amount)
+            .ok_or(EscrowError::Overflow)?;
+        self.count_deposit(timestamp)
+    }
+
+    /// Count a deposit without adding to the lamport total, returning its
+    /// sequence number (the seed of its `DepositReceipt`)
+    pub fn count_deposit(&mut self, timestamp: i64) -> Result<u32> {
+        let sequence = self.deposit_count;
+        self.deposit_count = sequence.checked_add(1).ok_or(EscrowError::Overflow)?;
+        self.last_deposit = timestamp;
+        Ok(sequence)
+    }
+}
+
+/// A sponsor's pending request to withdraw unallocated funds
+#[account]
+#[derive(InitSpace)]
+pub struct WithdrawalRequest {
+    /// Sponsor withdrawing (also the PDA seed)
+    pub sponsor: Pubkey,
+    /// Lamports requested (the payout may be lower if batches run first)
+    pub amount: u64,
+    /// Withdrawal shares redeemed on execution
+    pub shares: u128,
+    /// When the request was made
+    pub requested_at: i64,
+    /// Earliest execution time
+    pub executable_at: i64,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+/// On-chain copy of a single deposit, for sponsors to reconcile against
+#[account]
+#[derive(InitSpace)]
+pub struct DepositReceipt {
+    /// Depositor
+    pub sponsor: Pubkey,
+    /// Position in the sponsor's deposits (`deposit_count` before this one)
+    pub sequence: u32,
+    /// Lamports, or token units credited to the vault
+    pub amount: u64,
+    /// Token mint, or `None` for SOL
+    pub mint: Option<Pubkey>,
+    /// Free-form tag for the sponsor's own campaign tracking
+    #[max_len(MAX_CAMPAIGN_TAG_LEN)]
+    pub campaign_tag: Option<String>,
+    /// When the deposit was made
+    pub created_at: i64,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+/// An accepted token mint, its vault and its running totals
+#[account]
+#[derive(InitSpace)]
+pub struct MintConfig {
+    /// The token mint (also the PDA seed)
+    pub mint: Pubkey,
+    /// Associated token account of the escrow vault PDA holding this mint
+    pub vault: Pubkey,
+    /// Token program owning the mint (SPL Token or Token-2022)
+    pub token_program: Pubkey,
+    /// Mint decimals, cached for clients
+    pub decimals: u8,
+    /// Base units paid out per 1000 points
+    pub units_per_1000_points: u64,
+    /// Total base units deposited
+    pub total_deposited: u64,
+    /// Total base units received by NGOs
+    pub total_disbursed: u64,
+    /// Total transfer fees paid by the vault on top of disbursements
+    pub total_fees_withheld: u64,
+    /// Total points redeemed through token batches
+    pub total_points_redeemed: u64,
+    /// Last processed token batch week for this mint
+    pub last_batch_week: u64,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl MintConfig {
+    /// Add a disbursement and the transfer fee paid on it to the totals
+    pub fn record_disbursement(&mut self, amount: u64, fee: u64) -> Result<()> {
+        self.total_disbursed = self
+            .total_disbursed
+            .checked_add(amount)
+            .ok_or(EscrowError::Overflow)?;
+        self.total_fees_withheld = self
+            .total_fees_withheld
+            .checked_add(fee)
+            .ok_or(EscrowError::Overflow)?;
+        Ok(())
+    }
+}
+
+/// A sponsor deposit that may only be paid to matching NGOs
+#[account]
+#[derive(InitSpace)]
+pub struct Earmark {
+    /// Sequential earmark id (also the PDA seed)
+    pub id: u64,
+    /// Depositor
+    pub sponsor: Pubkey,
+    /// Which NGOs may receive these funds
+    pub restriction: EarmarkRestriction,
+    /// Lamports originally deposited
+    pub amount: u64,
+    /// Lamports not yet disbursed
+    pub remaining: u64,
+    /// When the deposit was made
+    pub created_at: i64,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+/// Restriction attached to an earmarked deposit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum EarmarkRestriction {
+    /// Only this NGO, named by its id
+    Ngo { ngo_id: u64 },
+    /// Any NGO in this cause category
+    Category { category: NgoCategory },
+}
+
+impl EarmarkRestriction {
+    /// Whether `ngo` may draw on it
+    pub fn allows(&self, ngo: &NgoAccount) -> bool {
+        match self {
+            EarmarkRestriction::Ngo { ngo_id } => ngo.id == *ngo_id,
+            EarmarkRestriction::Category { category } => ngo.category == *category,
+        }
+    }
+}
+
+/// A sponsor's offer to match batch disbursements to a set of NGOs
+#[account]
+#[derive(InitSpace)]
+pub struct MatchingCampaign {
+    /// Sequential campaign id (also the PDA seed)
+    pub id: u64,
+    /// Sponsor that funded the campaign (receives any refund)
+    pub sponsor: Pubkey,
+    /// NGOs eligible for matches (matched on current or original wallet)
+    #[max_len(MAX_CAMPAIGN_NGOS)]
+    pub ngos: Vec<Pubkey>,
+    /// Match as a share of the base disbursement (10000 = 1:1)
+    pub match_bps: u16,
+    /// Lamports deposited, the most the campaign will match
+    pub cap: u64,
+    /// Lamports matched so far
+    pub matched: u64,
+    /// Matches apply from this time...
+    pub starts_at: i64,
+    /// ...until this time (exclusive), after which funds can be reclaimed
+    pub ends_at: i64,
+    /// When the campaign was created
+    pub created_at: i64,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl MatchingCampaign {
+    pub fn remaining(&self) -> u64 {
+        self.cap - self.matched
+    }
+
+    pub fn is_running(&self, now: i64) -> bool {
+        self.starts_at <= now && now < self.ends_at
+    }
+
+    pub fn covers(&self, ngo: &NgoAccount) -> bool {
+        self.ngos.contains(&ngo.wallet) || self.ngos.contains(&ngo.original_wallet)
+    }
+}
+
+/// Entry for a single sponsor (brand partner) in the legacy registry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct SponsorEntry {
+    /// The sponsor's wallet address
+    pub pubkey: Pubkey,
+    /// Human-readable brand name (max 64 chars)
+    #[max_len(64)]
+    pub name: String,
+    /// Total lamports deposited by this sponsor
+    pub total_deposited: u64,
+    /// Number of deposits made
+    pub deposit_count: u32,
+    /// Timestamp of last deposit
+    pub last_deposit: i64,
+    /// Whether this sponsor is a verified partner
+    pub is_verified: bool,
+}
+
+/// Legacy registry of sponsors, replaced by `SponsorAccount`
+///
+/// No longer created by `initialize`; existing deployments drain it
+/// with `migrate_sponsor`.
+#[account]
+pub struct SponsorRegistry {
+    /// List of all registered sponsors
+    pub sponsors: Vec<SponsorEntry>,
     /// Bump seed for this PDA
     pub bump: u8,
-    /// Bump seed for the vault PDA
-    pub vault_bump: u8,
 }
 
-/// Entry for a single NGO in the registry
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
-pub struct NgoEntry {
-    /// The NGO's wallet address
-    pub pubkey: Pubkey,
-    /// Human-readable name (max 64 chars)
-    #[max_len(64)]
-    pub name: String,
-    /// Total lamports received by this NGO
-    pub total_received: u64,
-    /// Whether this NGO can receive disbursements
-    pub is_active: bool,
+impl SponsorRegistry {
+    pub const SPACE: usize = 8  // discriminator
+        + 4  // Vec length prefix
+        + (MAX_SPONSORS * (32 + 4 + 64 + 8 + 4 + 8 + 1))  // MAX_SPONSORS * SponsorEntry size
+        + 1; // bump
+}
+
+/// Allocation for a single NGO in a batch disbursement
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct BatchAllocation {
+    /// NGO wallet address
+    pub ngo: Pubkey,
+    /// Total points pledged to this NGO this week
+    pub points_pledged: u64,
+}
+
+/// Allocations to pay (with their index in the batch) and allocations
+/// rolled over for being under the minimum
+type PledgeSplit = (Vec<(usize, BatchAllocation)>, Vec<BatchAllocation>);
+
+/// Per-category totals within a batch (for events)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CategoryTotal {
+    /// Cause category
+    pub category: NgoCategory,
+    /// Points pledged to NGOs in this category
+    pub points_pledged: u64,
+    /// Lamports sent to NGOs in this category
+    pub amount_disbursed: u64,
+    /// Cap on the category's share of points the batch was checked
+    /// against, in basis points (10000 = uncapped)
+    pub max_share_bps: u16,
+}
+
+/// Detail of a single disbursement within a batch (for events)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DisbursementDetail {
+    /// NGO wallet address
+    pub ngo: Pubkey,
+    /// Points pledged by users
+    pub points_pledged: u64,
+    /// Base lamports sent (may be less due to pro-rata)
+    pub amount_disbursed: u64,
+    /// Lamports added on top by matching campaigns
+    pub amount_matched: u64,
+}
+
+/// SOL/USD price a batch was converted at (for events)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct PriceSnapshot {
+    /// Aggregate price, scaled by 10^expo USD per SOL
+    pub price: i64,
+    /// Confidence interval around the price, same scale
+    pub conf: u64,
+    /// Price exponent (e.g. -8)
+    pub expo: i32,
+    /// Unix time the price was published
+    pub publish_time: i64,
+}
+
+impl PriceSnapshot {
+    /// Read the aggregate price from a Pyth-layout price account, rejecting
+    /// it if it is not trading, older than `config.max_price_age` or less
+    /// certain than `config.max_price_confidence_bps` allows
+    pub fn read(info: &AccountInfo, config: &Config, now: i64) -> Result<Self> {
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() >= PYTH_PRICE_ACCOUNT_LEN
+                && u32::from_le_bytes(le_bytes(&data, 0)) == PYTH_MAGIC
+                && u32::from_le_bytes(le_bytes(&data, 4)) == PYTH_VERSION
+                && u32::from_le_bytes(le_bytes(&data, 8)) == PYTH_PRICE_ACCOUNT_TYPE,
+            EscrowError::InvalidPriceFeed
+        );
+        require!(
+            u32::from_le_bytes(le_bytes(&data, 224)) == PYTH_STATUS_TRADING,
+            EscrowError::PriceUnavailable
+        );
+
+        let snapshot = Self {
+            price: i64::from_le_bytes(le_bytes(&data, 208)),
+            conf: u64::from_le_bytes(le_bytes(&data, 216)),
+            expo: i32::from_le_bytes(le_bytes(&data, 20)),
+            publish_time: i64::from_le_bytes(le_bytes(&data, 96)),
+        };
+        require!(snapshot.price > 0, EscrowError::PriceUnavailable);
+        require!(
+            now.saturating_sub(snapshot.publish_time) <= config.max_price_age,
+            EscrowError::StalePrice
+        );
+        require!(
+            snapshot.conf as u128 * 10000
+                <= snapshot.price as u128 * config.max_price_confidence_bps as u128,
+            EscrowError::PriceConfidenceTooWide
+        );
+        Ok(snapshot)
+    }
+
+    /// Lamports per 1000 points at this price, where 100 points = $1
+    pub fn lamports_per_1000_points(&self) -> Result<u64> {
+        // 1000 points are worth $10, i.e. 10 * 10^9 / (price * 10^expo) lamports
+        let usd = (1000 / POINTS_PER_USD) as u128 * 1_000_000_000;
+        let scale = 10u128
+            .checked_pow(self.expo.unsigned_abs())
+            .ok_or(EscrowError::Overflow)?;
+        let price = self.price as u128;
+        let rate = if self.expo <= 0 {
+            usd.checked_mul(scale).ok_or(EscrowError::Overflow)? / price
+        } else {
+            usd / price.checked_mul(scale).ok_or(EscrowError::Overflow)?
+        };
+        require!(rate > 0, EscrowError::PriceUnavailable);
+        u64::try_from(rate).map_err(|_| EscrowError::Overflow.into())
+    }
+}
+
+// =============================================================================
+// INSTRUCTION CONTEXTS (Account Validation)
+// =============================================================================
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    /// The admin who will control the escrow
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Config account storing escrow settings
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The escrow vault PDA that holds deposited funds
+    /// CHECK: This is a PDA that will hold SOL, validated by seeds
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// The legacy admin (pays the extra rent)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Config account still in the legacy layout
+    /// CHECK: Owner and seeds checked here; layout checked in the handler
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump,
+        owner = crate::ID @ EscrowError::AccountMismatch
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    /// Only admins can create proposals
+    #[account(
+        mut,
+        constraint = config.is_admin(&proposer.key()) @ EscrowError::Unauthorized
+    )]
+    pub proposer: Signer<'info>,
+
+    /// Config to verify admin and assign the proposal id
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The new proposal
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [PROPOSAL_SEED, &config.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    /// Only admins can approve proposals
+    #[account(
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    /// Config to verify admin
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Proposal being approved
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    /// An admin or auditor (checked in instruction)
+    pub authority: Signer<'info>,
+
+    /// Config to verify admin
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Authority's role assignment, required when cancelling as auditor
+    #[account(
+        seeds = [ROLE_SEED, authority.key().as_ref()],
+        bump = authority_roles.bump
+    )]
+    pub authority_roles: Option<Account<'info, RoleAssignment>>,
+
+    /// Proposal being cancelled
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct SetAdmins<'info> {
+    /// Any admin can execute an approved proposal
+    #[account(
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    /// Config holding the admin set
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    /// Any admin can execute an approved proposal
+    #[account(
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    /// Config holding the pending handover
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    /// The pending admin accepting the seat
+    #[account(
+        mut,
+        constraint = config
+            .pending_admin
+            .as_ref()
+            .is_some_and(|p| p.new_admin == new_admin.key()) @ EscrowError::Unauthorized
+    )]
+    pub new_admin: Signer<'info>,
+
+    /// Config holding the admin set
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// History record for this handover
+    #[account(
+        init,
+        payer = new_admin,
+        space = 8 + AdminChangeRecord::INIT_SPACE,
+        seeds = [ADMIN_HISTORY_SEED, &config.admin_change_count.to_le_bytes()],
+        bump
+    )]
+    pub admin_change_record: Account<'info, AdminChangeRecord>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAdminHandover<'info> {
+    /// An admin or the pending admin
+    pub signer: Signer<'info>,
+
+    /// Config holding the pending handover
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+#[instruction(holder: Pubkey)]
+pub struct GrantRole<'info> {
+    /// Any admin can execute an approved proposal
+    #[account(
+        mut,
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    /// Config to verify admin
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Holder's role assignment (created on first grant)
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + RoleAssignment::INIT_SPACE,
+        seeds = [ROLE_SEED, holder.as_ref()],
+        bump
+    )]
+    pub role_assignment: Account<'info, RoleAssignment>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(holder: Pubkey)]
+pub struct RevokeRole<'info> {
+    /// Any admin can execute an approved proposal
+    #[account(
+        mut,
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    /// Config to verify admin
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Holder's role assignment
+    #[account(
+        mut,
+        seeds = [ROLE_SEED, holder.as_ref()],
+        bump = role_assignment.bump
+    )]
+    pub role_assignment: Account<'info, RoleAssignment>,
+}
+
+#[derive(Accounts)]
+pub struct SetTimelock<'info> {
+    /// Any admin can execute an approved proposal
+    #[account(
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    /// Config holding the timelock settings
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct SetWithdrawalNotice<'info> {
+    /// Any admin can execute an approved proposal
+    #[account(
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    /// Config holding the notice period
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct SetConversionRate<'info> {
+    /// Any admin can execute an approved proposal
+    #[account(
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    /// Config holding the conversion rate
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct SetVaultBuffer<'info> {
+    /// Any admin can execute an approved proposal
+    #[account(
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    /// Config holding the vault buffer
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct SetPledgeLimits<'info> {
+    /// Any admin can execute an approved proposal
+    #[account(
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    /// Config holding the pledge limits
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct SetPriceFeed<'info> {
+    /// Any admin can execute an approved proposal
+    #[account(
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    /// Config holding the price feed settings
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[cfg(feature = "mock-oracle")]
+#[derive(Accounts)]
+pub struct WriteMockPriceFeed<'info> {
+    /// Any admin can write the mock feed (pays for it on first write)
+    #[account(
+        mut,
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    /// Config to verify admin
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Mock SOL/USD feed in the Pyth price account layout
+    /// CHECK: Raw program-owned account, validated by seeds
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = PYTH_PRICE_ACCOUNT_LEN,
+        seeds = [MOCK_PRICE_FEED_SEED],
+        bump
+    )]
+    pub price_feed: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetCategoryCap<'info> {
+    /// Any admin can execute an approved proposal
+    #[account(
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    /// Config holding the category caps
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    /// Only a pauser can trip the circuit breaker
+    pub pauser: Signer<'info>,
+
+    /// Pauser's role assignment
+    #[account(
+        seeds = [ROLE_SEED, pauser.key().as_ref()],
+        bump = pauser_roles.bump,
+        constraint = pauser_roles.has(Role::Pauser) @ EscrowError::MissingRole
+    )]
+    pub pauser_roles: Account<'info, RoleAssignment>,
+
+    /// Config holding the pause flags
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct Unpause<'info> {
+    /// Any admin can execute an approved proposal
+    #[account(
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    /// Config holding the pause flags
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    /// The sponsor depositing funds
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    /// Config account to update totals
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Depositor's ledger, if they have one
+    #[account(
+        mut,
+        seeds = [SPONSOR_SEED, sponsor.key().as_ref()],
+        bump = sponsor_account.bump
+    )]
+    pub sponsor_account: Option<Account<'info, SponsorAccount>>,
+
+    /// Receipt for this deposit (requires `sponsor_account`)
+    #[account(
+        init,
+        payer = sponsor,
+        space = 8 + DepositReceipt::INIT_SPACE,
+        seeds = [
+            DEPOSIT_RECEIPT_SEED,
+            sponsor.key().as_ref(),
+            &sponsor_account.as_ref().map_or(0, |s| s.deposit_count).to_le_bytes()
+        ],
+        bump
+    )]
+    pub receipt: Option<Account<'info, DepositReceipt>>,
+
+    /// The escrow vault receiving the deposit
+    /// CHECK: Validated by seeds
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED],
+        bump = config.vault_bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositEarmarked<'info> {
+    /// The sponsor depositing funds (pays for the earmark account)
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    /// Config account to update totals and assign the earmark id
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Depositor's ledger, if they have one
+    #[account(
+        mut,
+        seeds = [SPONSOR_SEED, sponsor.key().as_ref()],
+        bump = sponsor_account.bump
+    )]
+    pub sponsor_account: Option<Account<'info, SponsorAccount>>,
+
+    /// Receipt for this deposit (requires `sponsor_account`)
+    #[account(
+        init,
+        payer = sponsor,
+        space = 8 + DepositReceipt::INIT_SPACE,
+        seeds = [
+            DEPOSIT_RECEIPT_SEED,
+            sponsor.key().as_ref(),
+            &sponsor_account.as_ref().map_or(0, |s| s.deposit_count).to_le_bytes()
+        ],
+        bump
+    )]
+    pub receipt: Option<Account<'info, DepositReceipt>>,
+
+    /// The NGO named by an NGO restriction (omit for category restrictions)
+    #[account(
+        seeds = [NGO_SEED, ngo_account.wallet.as_ref()],
+        bump = ngo_account.bump,
+        constraint = ngo_account.is_active @ EscrowError::NgoNotActive
+    )]
+    pub ngo_account: Option<Account<'info, NgoAccount>>,
+
+    /// The new earmark
+    #[account(
+        init,
+        payer = sponsor,
+        space = 8 + Earmark::INIT_SPACE,
+        seeds = [EARMARK_SEED, &config.earmark_count.to_le_bytes()],
+        bump
+    )]
+    pub earmark: Account<'info, Earmark>,
+
+    /// The escrow vault receiving the deposit
+    /// CHECK: Validated by seeds
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED],
+        bump = config.vault_bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateMatchingCampaign<'info> {
+    /// The sponsor funding the campaign (pays for the campaign account)
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    /// Config account to reserve the funds and assign the campaign id
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The new campaign
+    #[account(
+        init,
+        payer = sponsor,
+        space = 8 + MatchingCampaign::INIT_SPACE,
+        seeds = [MATCHING_CAMPAIGN_SEED, &config.campaign_count.to_le_bytes()],
+        bump
+    )]
+    pub campaign: Account<'info, MatchingCampaign>,
+
+    /// The escrow vault receiving the match funds
+    /// CHECK: Validated by seeds
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED],
+        bump = config.vault_bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseMatchingCampaign<'info> {
+    /// The sponsor that funded the campaign
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    /// Config account to release the reserved funds
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The ended campaign (closed, rent returned to the sponsor)
+    #[account(
+        mut,
+        seeds = [MATCHING_CAMPAIGN_SEED, &campaign.id.to_le_bytes()],
+        bump = campaign.bump,
+        has_one = sponsor @ EscrowError::Unauthorized,
+        close = sponsor
+    )]
+    pub campaign: Account<'info, MatchingCampaign>,
+
+    /// The escrow vault refunding unused funds
+    /// CHECK: Validated by seeds
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED],
+        bump = config.vault_bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RefundEarmark<'info> {
+    /// Any admin can execute an approved proposal
+    #[account(
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    /// Config account to release the earmarked funds
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// The earmark being refunded (closed, rent returned to the sponsor)
+    #[account(
+        mut,
+        seeds = [EARMARK_SEED, &earmark.id.to_le_bytes()],
+        bump = earmark.bump,
+        has_one = sponsor @ EscrowError::AccountMismatch,
+        close = sponsor
+    )]
+    pub earmark: Account<'info, Earmark>,
+
+    /// The sponsor that made the earmarked deposit
+    #[account(mut)]
+    pub sponsor: SystemAccount<'info>,
+
+    /// The escrow vault refunding the unspent funds
+    /// CHECK: Validated by seeds
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED],
+        bump = config.vault_bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseDepositReceipt<'info> {
+    /// The sponsor the receipt belongs to (receives the rent)
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    /// The receipt to close
+    #[account(
+        mut,
+        seeds = [DEPOSIT_RECEIPT_SEED, sponsor.key().as_ref(), &receipt.sequence.to_le_bytes()],
+        bump = receipt.bump,
+        has_one = sponsor @ EscrowError::Unauthorized,
+        close = sponsor
+    )]
+    pub receipt: Account<'info, DepositReceipt>,
+}
+
+#[derive(Accounts)]
+pub struct RequestWithdrawal<'info> {
+    /// The sponsor withdrawing (pays for the request account)
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    /// Config holding the share totals and notice period
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The sponsor's ledger holding its shares
+    #[account(
+        seeds = [SPONSOR_SEED, sponsor.key().as_ref()],
+        bump = sponsor_account.bump
+    )]
+    pub sponsor_account: Account<'info, SponsorAccount>,
+
+    /// The new request
+    #[account(
+        init,
+        payer = sponsor,
+        space = 8 + WithdrawalRequest::INIT_SPACE,
+        seeds = [WITHDRAWAL_SEED, sponsor.key().as_ref()],
+        bump
+    )]
+    pub withdrawal: Account<'info, WithdrawalRequest>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteWithdrawal<'info> {
+    /// The sponsor that made the request (receives the funds and rent)
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    /// Config account to update share totals
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The sponsor's ledger holding its shares
+    #[account(
+        mut,
+        seeds = [SPONSOR_SEED, sponsor.key().as_ref()],
+        bump = sponsor_account.bump
+    )]
+    pub sponsor_account: Account<'info, SponsorAccount>,
+
+    /// The request being executed (closed afterwards)
+    #[account(
+        mut,
+        seeds = [WITHDRAWAL_SEED, sponsor.key().as_ref()],
+        bump = withdrawal.bump,
+        close = sponsor
+    )]
+    pub withdrawal: Account<'info, WithdrawalRequest>,
+
+    /// The escrow vault paying out
+    /// CHECK: Validated by seeds
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED],
+        bump = config.vault_bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelWithdrawal<'info> {
+    /// The sponsor that made the request (receives the rent)
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    /// The request being cancelled
+    #[account(
+        mut,
+        seeds = [WITHDRAWAL_SEED, sponsor.key().as_ref()],
+        bump = withdrawal.bump,
+        close = sponsor
+    )]
+    pub withdrawal: Account<'info, WithdrawalRequest>,
+}
+
+#[derive(Accounts)]
+pub struct OpenSponsorLedger<'info> {
+    /// The depositor opening their own ledger
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    /// The new anonymous ledger
+    #[account(
+        init,
+        payer = sponsor,
+        space = 8 + SponsorAccount::INIT_SPACE,
+        seeds = [SPONSOR_SEED, sponsor.key().as_ref()],
+        bump
+    )]
+    pub sponsor_account: Account<'info, SponsorAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(sponsor_pubkey: Pubkey)]
+pub struct RegisterSponsor<'info> {
+    /// Only a registry manager can register sponsors
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    /// Operator's role assignment
+    #[account(
+        seeds = [ROLE_SEED, operator.key().as_ref()],
+        bump = operator_roles.bump,
+        constraint = operator_roles.has(Role::RegistryManager) @ EscrowError::MissingRole
+    )]
+    pub operator_roles: Account<'info, RoleAssignment>,
+
+    /// Config to verify the proposal
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Sponsor's account (created, or upgraded from an anonymous ledger)
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = 8 + SponsorAccount::INIT_SPACE,
+        seeds = [SPONSOR_SEED, sponsor_pubkey.as_ref()],
+        bump
+    )]
+    pub sponsor_account: Account<'info, SponsorAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(sponsor_pubkey: Pubkey)]
+pub struct RemoveSponsor<'info> {
+    /// Only a registry manager can remove sponsors
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    /// Operator's role assignment
+    #[account(
+        seeds = [ROLE_SEED, operator.key().as_ref()],
+        bump = operator_roles.bump,
+        constraint = operator_roles.has(Role::RegistryManager) @ EscrowError::MissingRole
+    )]
+    pub operator_roles: Account<'info, RoleAssignment>,
+
+    /// Config to verify the proposal
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Sponsor account to update
+    #[account(
+        mut,
+        seeds = [SPONSOR_SEED, sponsor_pubkey.as_ref()],
+        bump = sponsor_account.bump
+    )]
+    pub sponsor_account: Account<'info, SponsorAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(sponsor_pubkey: Pubkey)]
+pub struct ReverifySponsor<'info> {
+    /// Only a registry manager can re-verify sponsors
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    /// Operator's role assignment
+    #[account(
+        seeds = [ROLE_SEED, operator.key().as_ref()],
+        bump = operator_roles.bump,
+        constraint = operator_roles.has(Role::RegistryManager) @ EscrowError::MissingRole
+    )]
+    pub operator_roles: Account<'info, RoleAssignment>,
+
+    /// Config to verify the proposal
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Sponsor account to update
+    #[account(
+        mut,
+        seeds = [SPONSOR_SEED, sponsor_pubkey.as_ref()],
+        bump = sponsor_account.bump
+    )]
+    pub sponsor_account: Account<'info, SponsorAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(ngo_pubkey: Pubkey)]
+pub struct AddNgo<'info> {
+    /// Only a registry manager can add NGOs
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    /// The NGO wallet being whitelisted, co-signing to prove control
+    #[account(address = ngo_pubkey @ EscrowError::AccountMismatch)]
+    pub ngo: Signer<'info>,
+
+    /// Operator's role assignment
+    #[account(
+        seeds = [ROLE_SEED, operator.key().as_ref()],
+        bump = operator_roles.bump,
+        constraint = operator_roles.has(Role::RegistryManager) @ EscrowError::MissingRole
+    )]
+    pub operator_roles: Account<'info, RoleAssignment>,
+
+    /// Config to verify the proposal and issue the NGO id
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// The new NGO's account
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = 8 + NgoAccount::INIT_SPACE,
+        seeds = [NGO_SEED, ngo_pubkey.as_ref()],
+        bump
+    )]
+    pub ngo_account: Account<'info, NgoAccount>,
+
+    pub system_program: Program<'info, System>,
 }
 
-/// Registry of all whitelisted NGOs
-#[account]
-pub struct NgoRegistry {
-    /// List of all registered NGOs
-    pub ngos: Vec<NgoEntry>,
-    /// Bump seed for this PDA
-    pub bump: u8,
+#[derive(Accounts)]
+pub struct ApplyNgo<'info> {
+    /// The applying NGO wallet (pays for the application)
+    #[account(mut)]
+    pub ngo: Signer<'info>,
+
+    /// Config to check the registry pause flag
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The NGO must not already be whitelisted
+    /// CHECK: Only checked to be empty
+    #[account(
+        seeds = [NGO_SEED, ngo.key().as_ref()],
+        bump,
+        constraint = ngo_account.data_is_empty() @ EscrowError::NgoAlreadyExists
+    )]
+    pub ngo_account: UncheckedAccount<'info>,
+
+    /// The new application
+    #[account(
+        init,
+        payer = ngo,
+        space = 8 + NgoApplication::INIT_SPACE,
+        seeds = [NGO_APPLICATION_SEED, ngo.key().as_ref()],
+        bump
+    )]
+    pub application: Account<'info, NgoApplication>,
+
+    pub system_program: Program<'info, System>,
 }
 
-impl NgoRegistry {
-    pub const SPACE: usize = 8  // discriminator
-        + 4  // Vec length prefix
-        + (MAX_NGOS * (32 + 4 + 64 + 8 + 1))  // MAX_NGOS * NgoEntry size
-        + 1; // bump
+#[derive(Accounts)]
+#[instruction(ngo_pubkey: Pubkey)]
+pub struct ApproveNgoApplication<'info> {
+    /// Only a registry manager can approve applications
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    /// Operator's role assignment
+    #[account(
+        seeds = [ROLE_SEED, operator.key().as_ref()],
+        bump = operator_roles.bump,
+        constraint = operator_roles.has(Role::RegistryManager) @ EscrowError::MissingRole
+    )]
+    pub operator_roles: Account<'info, RoleAssignment>,
+
+    /// Config to verify the proposal and issue the NGO id
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// The applicant, refunded the application rent
+    /// CHECK: Must match the application's wallet
+    #[account(mut, address = ngo_pubkey @ EscrowError::AccountMismatch)]
+    pub ngo: UncheckedAccount<'info>,
+
+    /// The pending application (closed)
+    #[account(
+        mut,
+        close = ngo,
+        seeds = [NGO_APPLICATION_SEED, ngo_pubkey.as_ref()],
+        bump = application.bump
+    )]
+    pub application: Account<'info, NgoApplication>,
+
+    /// The new NGO account
+    #[account(
+        init,
+        payer = operator,
+        space = 8 + NgoAccount::INIT_SPACE,
+        seeds = [NGO_SEED, ngo_pubkey.as_ref()],
+        bump
+    )]
+    pub ngo_account: Account<'info, NgoAccount>,
+
+    pub system_program: Program<'info, System>,
 }
 
-/// Entry for a single sponsor (brand partner) in the registry
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
-pub struct SponsorEntry {
-    /// The sponsor's wallet address
-    pub pubkey: Pubkey,
-    /// Human-readable brand name (max 64 chars)
-    #[max_len(64)]
-    pub name: String,
-    /// Total lamports deposited by this sponsor
-    pub total_deposited: u64,
-    /// Number of deposits made
-    pub deposit_count: u32,
-    /// Timestamp of last deposit
-    pub last_deposit: i64,
-    /// Whether this sponsor is a verified partner
-    pub is_verified: bool,
+#[derive(Accounts)]
+#[instruction(ngo_pubkey: Pubkey)]
+pub struct RejectNgoApplication<'info> {
+    /// Only a registry manager can reject applications
+    pub operator: Signer<'info>,
+
+    /// Operator's role assignment
+    #[account(
+        seeds = [ROLE_SEED, operator.key().as_ref()],
+        bump = operator_roles.bump,
+        constraint = operator_roles.has(Role::RegistryManager) @ EscrowError::MissingRole
+    )]
+    pub operator_roles: Account<'info, RoleAssignment>,
+
+    /// The applicant, refunded the application rent
+    /// CHECK: Must match the application's wallet
+    #[account(mut, address = ngo_pubkey @ EscrowError::AccountMismatch)]
+    pub ngo: UncheckedAccount<'info>,
+
+    /// The pending application (closed)
+    #[account(
+        mut,
+        close = ngo,
+        seeds = [NGO_APPLICATION_SEED, ngo_pubkey.as_ref()],
+        bump = application.bump
+    )]
+    pub application: Account<'info, NgoApplication>,
 }
 
-/// Registry of all registered sponsors (brand partners)
-#[account]
-pub struct SponsorRegistry {
-    /// List of all registered sponsors
-    pub sponsors: Vec<SponsorEntry>,
-    /// Bump seed for this PDA
-    pub bump: u8,
+#[derive(Accounts)]
+#[instruction(ngo_pubkey: Pubkey)]
+pub struct RemoveNgo<'info> {
+    /// Only a registry manager can remove NGOs
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    /// Operator's role assignment
+    #[account(
+        seeds = [ROLE_SEED, operator.key().as_ref()],
+        bump = operator_roles.bump,
+        constraint = operator_roles.has(Role::RegistryManager) @ EscrowError::MissingRole
+    )]
+    pub operator_roles: Account<'info, RoleAssignment>,
+
+    /// Config to verify the proposal
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// NGO account to update
+    #[account(
+        mut,
+        seeds = [NGO_SEED, ngo_pubkey.as_ref()],
+        bump = ngo_account.bump
+    )]
+    pub ngo_account: Account<'info, NgoAccount>,
 }
 
-impl SponsorRegistry {
-    pub const SPACE: usize = 8  // discriminator
-        + 4  // Vec length prefix
-        + (MAX_SPONSORS * (32 + 4 + 64 + 8 + 4 + 8 + 1))  // MAX_SPONSORS * SponsorEntry size
-        + 1; // bump
+#[derive(Accounts)]
+#[instruction(ngo_pubkey: Pubkey)]
+pub struct ReactivateNgo<'info> {
+    /// Only a registry manager can reactivate NGOs
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    /// Operator's role assignment
+    #[account(
+        seeds = [ROLE_SEED, operator.key().as_ref()],
+        bump = operator_roles.bump,
+        constraint = operator_roles.has(Role::RegistryManager) @ EscrowError::MissingRole
+    )]
+    pub operator_roles: Account<'info, RoleAssignment>,
+
+    /// Config to verify the proposal
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// NGO account to update
+    #[account(
+        mut,
+        seeds = [NGO_SEED, ngo_pubkey.as_ref()],
+        bump = ngo_account.bump
+    )]
+    pub ngo_account: Account<'info, NgoAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(ngo_pubkey: Pubkey)]
+pub struct UpdateNgoProfile<'info> {
+    /// Only a registry manager can update NGO profiles
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    /// Operator's role assignment
+    #[account(
+        seeds = [ROLE_SEED, operator.key().as_ref()],
+        bump = operator_roles.bump,
+        constraint = operator_roles.has(Role::RegistryManager) @ EscrowError::MissingRole
+    )]
+    pub operator_roles: Account<'info, RoleAssignment>,
+
+    /// Config to verify the proposal
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// NGO account to update
+    #[account(
+        mut,
+        seeds = [NGO_SEED, ngo_pubkey.as_ref()],
+        bump = ngo_account.bump
+    )]
+    pub ngo_account: Account<'info, NgoAccount>,
 }
 
-/// Allocation for a single NGO in a batch disbursement
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct BatchAllocation {
-    /// NGO wallet address
-    pub ngo: Pubkey,
-    /// Total points pledged to this NGO this week
-    pub points_pledged: u64,
-}
+#[derive(Accounts)]
+#[instruction(ngo_pubkey: Pubkey)]
+pub struct RenewNgoVerification<'info> {
+    /// Only a registry manager can renew verifications
+    pub operator: Signer<'info>,
+
+    /// Operator's role assignment
+    #[account(
+        seeds = [ROLE_SEED, operator.key().as_ref()],
+        bump = operator_roles.bump,
+        constraint = operator_roles.has(Role::RegistryManager) @ EscrowError::MissingRole
+    )]
+    pub operator_roles: Account<'info, RoleAssignment>,
+
+    /// Config to verify the proposal
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// NGO account to renew
+    #[account(
+        mut,
+        seeds = [NGO_SEED, ngo_pubkey.as_ref()],
+        bump = ngo_account.bump
+    )]
+    pub ngo_account: Account<'info, NgoAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(ngo_pubkey: Pubkey)]
+pub struct ArchiveNgo<'info> {
+    /// Any admin can execute an approved proposal (receives the rent)
+    #[account(
+        mut,
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    /// Config to verify the proposal
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Deactivated NGO account (closed)
+    #[account(
+        mut,
+        close = admin,
+        seeds = [NGO_SEED, ngo_pubkey.as_ref()],
+        bump = ngo_account.bump
+    )]
+    pub ngo_account: Account<'info, NgoAccount>,
+
+    /// Marker for the archived wallet
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + RetiredNgoWallet::INIT_SPACE,
+        seeds = [RETIRED_NGO_WALLET_SEED, ngo_pubkey.as_ref()],
+        bump
+    )]
+    pub retired_wallet: Account<'info, RetiredNgoWallet>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(sponsor_pubkey: Pubkey)]
+pub struct ArchiveSponsor<'info> {
+    /// Any admin can execute an approved proposal (receives the rent)
+    #[account(
+        mut,
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    /// Config to verify the proposal and release the sponsor's shares
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Unverified sponsor account (closed)
+    #[account(
+        mut,
+        close = admin,
+        seeds = [SPONSOR_SEED, sponsor_pubkey.as_ref()],
+        bump = sponsor_account.bump
+    )]
+    pub sponsor_account: Account<'info, SponsorAccount>,
 
-/// Detail of a single disbursement within a batch (for events)
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct DisbursementDetail {
-    /// NGO wallet address
-    pub ngo: Pubkey,
-    /// Points pledged by users
-    pub points_pledged: u64,
-    /// Actual lamports sent (may be less due to pro-rata)
-    pub amount_disbursed: u64,
+    /// The sponsor must not have a pending withdrawal request
+    /// CHECK: Only checked to be empty
+    #[account(
+        seeds = [WITHDRAWAL_SEED, sponsor_pubkey.as_ref()],
+        bump,
+        constraint = withdrawal.data_is_empty() @ EscrowError::WithdrawalPending
+    )]
+    pub withdrawal: UncheckedAccount<'info>,
 }
 
-// =============================================================================
-// INSTRUCTION CONTEXTS (Account Validation)
-// =============================================================================
-
 #[derive(Accounts)]
-pub struct Initialize<'info> {
-    /// The admin who will control the escrow
-    #[account(mut)]
+#[instruction(new_wallet: Pubkey)]
+pub struct RotateNgoWallet<'info> {
+    /// Any admin can execute an approved proposal (pays for the new account)
+    #[account(
+        mut,
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized
+    )]
     pub admin: Signer<'info>,
 
-    /// Config account storing escrow settings
+    /// The NGO's current wallet, consenting to the rotation
+    pub ngo: Signer<'info>,
+
+    /// The new wallet, co-signing to prove control
+    #[account(address = new_wallet @ EscrowError::AccountMismatch)]
+    pub new_ngo: Signer<'info>,
+
+    /// Config to verify the proposal
     #[account(
-        init,
-        payer = admin,
-        space = 8 + Config::INIT_SPACE,
         seeds = [CONFIG_SEED],
-        bump
+        bump = config.bump
     )]
     pub config: Account<'info, Config>,
 
-    /// Registry of whitelisted NGOs
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Active NGO account for the current wallet (closed)
+    #[account(
+        mut,
+        close = admin,
+        seeds = [NGO_SEED, ngo.key().as_ref()],
+        bump = old_ngo_account.bump,
+        constraint = old_ngo_account.is_active @ EscrowError::NgoNotActive
+    )]
+    pub old_ngo_account: Account<'info, NgoAccount>,
+
+    /// NGO account for the new wallet
     #[account(
         init,
         payer = admin,
-        space = NgoRegistry::SPACE,
-        seeds = [NGO_REGISTRY_SEED],
+        space = 8 + NgoAccount::INIT_SPACE,
+        seeds = [NGO_SEED, new_wallet.as_ref()],
         bump
     )]
-    pub ngo_registry: Account<'info, NgoRegistry>,
+    pub new_ngo_account: Account<'info, NgoAccount>,
 
-    /// Registry of verified sponsors (brand partners)
+    /// Marker for the wallet being rotated away from
     #[account(
-        init,
+        init_if_needed,
         payer = admin,
-        space = SponsorRegistry::SPACE,
-        seeds = [SPONSOR_REGISTRY_SEED],
+        space = 8 + RetiredNgoWallet::INIT_SPACE,
+        seeds = [RETIRED_NGO_WALLET_SEED, ngo.key().as_ref()],
         bump
     )]
-    pub sponsor_registry: Account<'info, SponsorRegistry>,
+    pub retired_wallet: Account<'info, RetiredNgoWallet>,
 
-    /// The escrow vault PDA that holds deposited funds
-    /// CHECK: This is a PDA that will hold SOL, validated by seeds
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Disburse<'info> {
+    /// Only a disburser can disburse
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    /// Operator's role assignment
+    #[account(
+        seeds = [ROLE_SEED, operator.key().as_ref()],
+        bump = operator_roles.bump,
+        constraint = operator_roles.has(Role::Disburser) @ EscrowError::MissingRole
+    )]
+    pub operator_roles: Account<'info, RoleAssignment>,
+
+    /// Config to verify the proposal and update totals
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal authorizing this action
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Recipient's NGO account
+    #[account(
+        mut,
+        seeds = [NGO_SEED, ngo.key().as_ref()],
+        bump = ngo_account.bump
+    )]
+    pub ngo_account: Account<'info, NgoAccount>,
+
+    /// The escrow vault sending funds
+    /// CHECK: Validated by seeds
     #[account(
         mut,
         seeds = [ESCROW_SEED],
-        bump
+        bump = config.vault_bump
     )]
     pub escrow_vault: SystemAccount<'info>,
 
+    /// The NGO receiving the disbursement
+    /// CHECK: Validated by the ngo_account seeds
+    #[account(mut)]
+    pub ngo: SystemAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct Deposit<'info> {
-    /// The sponsor depositing funds
+pub struct BatchDisburse<'info> {
+    /// Only a disburser can trigger batch disbursements
     #[account(mut)]
-    pub sponsor: Signer<'info>,
+    pub operator: Signer<'info>,
 
-    /// Config account to update totals
+    /// Operator's role assignment
+    #[account(
+        seeds = [ROLE_SEED, operator.key().as_ref()],
+        bump = operator_roles.bump,
+        constraint = operator_roles.has(Role::Disburser) @ EscrowError::MissingRole
+    )]
+    pub operator_roles: Account<'info, RoleAssignment>,
+
+    /// Config to verify the proposal, update totals, and track batch week
     #[account(
         mut,
         seeds = [CONFIG_SEED],
@@ -714,15 +6065,15 @@ pub struct Deposit<'info> {
     )]
     pub config: Account<'info, Config>,
 
-    /// Sponsor registry to update sponsor totals
+    /// Approved proposal authorizing this action
     #[account(
         mut,
-        seeds = [SPONSOR_REGISTRY_SEED],
-        bump = sponsor_registry.bump
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
     )]
-    pub sponsor_registry: Account<'info, SponsorRegistry>,
+    pub proposal: Account<'info, Proposal>,
 
-    /// The escrow vault receiving the deposit
+    /// The escrow vault sending funds
     /// CHECK: Validated by seeds
     #[account(
         mut,
@@ -731,119 +6082,295 @@ pub struct Deposit<'info> {
     )]
     pub escrow_vault: SystemAccount<'info>,
 
+    /// SOL/USD feed, required when `Config.price_feed` is set
+    /// CHECK: Address checked against the config, layout by `PriceSnapshot::read`
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
     pub system_program: Program<'info, System>,
+    // NGO wallets and their NgoAccount PDAs, then any Earmark accounts,
+    // are passed as remaining_accounts
 }
 
 #[derive(Accounts)]
-pub struct RegisterSponsor<'info> {
-    /// Only the admin can register sponsors
+pub struct AddMint<'info> {
+    /// Any admin can execute an approved proposal (pays for the accounts)
     #[account(
         mut,
-        constraint = admin.key() == config.admin @ EscrowError::Unauthorized
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized
     )]
     pub admin: Signer<'info>,
 
-    /// Config to verify admin
+    /// Config to verify the proposal
     #[account(
         seeds = [CONFIG_SEED],
         bump = config.bump
     )]
     pub config: Account<'info, Config>,
 
-    /// Sponsor registry to update
+    /// Approved proposal authorizing this action
     #[account(
         mut,
-        seeds = [SPONSOR_REGISTRY_SEED],
-        bump = sponsor_registry.bump
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
     )]
-    pub sponsor_registry: Account<'info, SponsorRegistry>,
-}
+    pub proposal: Account<'info, Proposal>,
 
-#[derive(Accounts)]
-pub struct RemoveSponsor<'info> {
-    /// Only the admin can remove sponsors
+    /// The token mint being accepted (SPL Token or Token-2022)
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Stats account for the mint
     #[account(
-        mut,
-        constraint = admin.key() == config.admin @ EscrowError::Unauthorized
+        init,
+        payer = admin,
+        space = 8 + MintConfig::INIT_SPACE,
+        seeds = [MINT_CONFIG_SEED, mint.key().as_ref()],
+        bump
     )]
-    pub admin: Signer<'info>,
+    pub mint_config: Account<'info, MintConfig>,
 
-    /// Config to verify admin
+    /// The escrow vault PDA, authority of every token vault
+    #[account(
+        seeds = [ESCROW_SEED],
+        bump = config.vault_bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+
+    /// The new token vault
+    #[account(
+        init,
+        payer = admin,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositToken<'info> {
+    /// The depositor (pays for the receipt, if any)
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    /// Config to check the deposit pause flag
     #[account(
         seeds = [CONFIG_SEED],
         bump = config.bump
     )]
     pub config: Account<'info, Config>,
 
-    /// Sponsor registry to update
+    /// Depositor's ledger, if they have one
     #[account(
         mut,
-        seeds = [SPONSOR_REGISTRY_SEED],
-        bump = sponsor_registry.bump
+        seeds = [SPONSOR_SEED, sponsor.key().as_ref()],
+        bump = sponsor_account.bump
     )]
-    pub sponsor_registry: Account<'info, SponsorRegistry>,
+    pub sponsor_account: Option<Account<'info, SponsorAccount>>,
+
+    /// Receipt for this deposit (requires `sponsor_account`)
+    #[account(
+        init,
+        payer = sponsor,
+        space = 8 + DepositReceipt::INIT_SPACE,
+        seeds = [
+            DEPOSIT_RECEIPT_SEED,
+            sponsor.key().as_ref(),
+            &sponsor_account.as_ref().map_or(0, |s| s.deposit_count).to_le_bytes()
+        ],
+        bump
+    )]
+    pub receipt: Option<Account<'info, DepositReceipt>>,
+
+    /// Stats account of the deposited mint
+    #[account(
+        mut,
+        seeds = [MINT_CONFIG_SEED, mint_config.mint.as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    /// The mint being moved
+    #[account(address = mint_config.mint @ EscrowError::AccountMismatch)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The depositor's token account for this mint
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = sponsor,
+        token::token_program = token_program
+    )]
+    pub sponsor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The mint's vault
+    #[account(
+        mut,
+        address = mint_config.vault @ EscrowError::AccountMismatch
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program owning the mint
+    #[account(address = mint_config.token_program @ EscrowError::AccountMismatch)]
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct AddNgo<'info> {
-    /// Only the admin can add NGOs
+pub struct DisburseToken<'info> {
+    /// Only a disburser can disburse
+    pub operator: Signer<'info>,
+
+    /// Operator's role assignment
     #[account(
-        mut,
-        constraint = admin.key() == config.admin @ EscrowError::Unauthorized
+        seeds = [ROLE_SEED, operator.key().as_ref()],
+        bump = operator_roles.bump,
+        constraint = operator_roles.has(Role::Disburser) @ EscrowError::MissingRole
     )]
-    pub admin: Signer<'info>,
+    pub operator_roles: Account<'info, RoleAssignment>,
 
-    /// Config to verify admin
+    /// Config to verify the proposal
     #[account(
         seeds = [CONFIG_SEED],
         bump = config.bump
     )]
     pub config: Account<'info, Config>,
 
-    /// NGO registry to update
+    /// Approved proposal authorizing this action
     #[account(
         mut,
-        seeds = [NGO_REGISTRY_SEED],
-        bump = ngo_registry.bump
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
     )]
-    pub ngo_registry: Account<'info, NgoRegistry>,
+    pub proposal: Account<'info, Proposal>,
+
+    /// Stats account of the disbursed mint
+    #[account(
+        mut,
+        seeds = [MINT_CONFIG_SEED, mint_config.mint.as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    /// Recipient's NGO account
+    #[account(
+        seeds = [NGO_SEED, ngo_token_account.owner.as_ref()],
+        bump = ngo_account.bump
+    )]
+    pub ngo_account: Account<'info, NgoAccount>,
+
+    /// The mint being moved
+    #[account(address = mint_config.mint @ EscrowError::AccountMismatch)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The escrow vault PDA, authority of the token vault
+    #[account(
+        seeds = [ESCROW_SEED],
+        bump = config.vault_bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+
+    /// The mint's vault
+    #[account(
+        mut,
+        address = mint_config.vault @ EscrowError::AccountMismatch
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The NGO's token account for this mint
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program
+    )]
+    pub ngo_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program owning the mint
+    #[account(address = mint_config.token_program @ EscrowError::AccountMismatch)]
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
-pub struct RemoveNgo<'info> {
-    /// Only the admin can remove NGOs
+pub struct BatchDisburseToken<'info> {
+    /// Only a disburser can trigger batch disbursements
+    pub operator: Signer<'info>,
+
+    /// Operator's role assignment
     #[account(
-        mut,
-        constraint = admin.key() == config.admin @ EscrowError::Unauthorized
+        seeds = [ROLE_SEED, operator.key().as_ref()],
+        bump = operator_roles.bump,
+        constraint = operator_roles.has(Role::Disburser) @ EscrowError::MissingRole
     )]
-    pub admin: Signer<'info>,
+    pub operator_roles: Account<'info, RoleAssignment>,
 
-    /// Config to verify admin
+    /// Config to verify the proposal and read category caps
     #[account(
         seeds = [CONFIG_SEED],
         bump = config.bump
     )]
     pub config: Account<'info, Config>,
 
-    /// NGO registry to update
+    /// Approved proposal authorizing this action
     #[account(
         mut,
-        seeds = [NGO_REGISTRY_SEED],
-        bump = ngo_registry.bump
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
     )]
-    pub ngo_registry: Account<'info, NgoRegistry>,
+    pub proposal: Account<'info, Proposal>,
+
+    /// Stats account of the disbursed mint (tracks batch weeks)
+    #[account(
+        mut,
+        seeds = [MINT_CONFIG_SEED, mint_config.mint.as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    /// The mint being moved
+    #[account(address = mint_config.mint @ EscrowError::AccountMismatch)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The escrow vault PDA, authority of the token vault
+    #[account(
+        seeds = [ESCROW_SEED],
+        bump = config.vault_bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+
+    /// The mint's vault
+    #[account(
+        mut,
+        address = mint_config.vault @ EscrowError::AccountMismatch
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program owning the mint
+    #[account(address = mint_config.token_program @ EscrowError::AccountMismatch)]
+    pub token_program: Interface<'info, TokenInterface>,
+    // NGO token accounts and their NgoAccount PDAs are passed as remaining_accounts
 }
 
 #[derive(Accounts)]
-pub struct Disburse<'info> {
-    /// Only the admin can disburse
+#[instruction(ngo_pubkey: Pubkey)]
+pub struct MigrateNgo<'info> {
+    /// Only a registry manager can migrate NGOs
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    /// Operator's role assignment
     #[account(
-        mut,
-        constraint = admin.key() == config.admin @ EscrowError::Unauthorized
+        seeds = [ROLE_SEED, operator.key().as_ref()],
+        bump = operator_roles.bump,
+        constraint = operator_roles.has(Role::RegistryManager) @ EscrowError::MissingRole
     )]
-    pub admin: Signer<'info>,
+    pub operator_roles: Account<'info, RoleAssignment>,
 
-    /// Config to verify admin and update totals
+    /// Config to issue the NGO id
     #[account(
         mut,
         seeds = [CONFIG_SEED],
@@ -851,7 +6378,7 @@ pub struct Disburse<'info> {
     )]
     pub config: Account<'info, Config>,
 
-    /// NGO registry to verify recipient
+    /// Legacy NGO registry being drained
     #[account(
         mut,
         seeds = [NGO_REGISTRY_SEED],
@@ -859,59 +6386,102 @@ pub struct Disburse<'info> {
     )]
     pub ngo_registry: Account<'info, NgoRegistry>,
 
-    /// The escrow vault sending funds
-    /// CHECK: Validated by seeds
+    /// The migrated NGO's account (created unless it was whitelisted again)
     #[account(
-        mut,
-        seeds = [ESCROW_SEED],
-        bump = config.vault_bump
+        init_if_needed,
+        payer = operator,
+        space = 8 + NgoAccount::INIT_SPACE,
+        seeds = [NGO_SEED, ngo_pubkey.as_ref()],
+        bump
     )]
-    pub escrow_vault: SystemAccount<'info>,
+    pub ngo_account: Account<'info, NgoAccount>,
 
-    /// The NGO receiving the disbursement
-    /// CHECK: Validated against ngo_registry in instruction
+    /// Marker present if the wallet was rotated away from or archived
+    /// CHECK: Only checked for existence
+    #[account(
+        seeds = [RETIRED_NGO_WALLET_SEED, ngo_pubkey.as_ref()],
+        bump
+    )]
+    pub retired_wallet: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(sponsor_pubkey: Pubkey)]
+pub struct MigrateSponsor<'info> {
+    /// Only a registry manager can migrate sponsors
     #[account(mut)]
-    pub ngo: SystemAccount<'info>,
+    pub operator: Signer<'info>,
+
+    /// Operator's role assignment
+    #[account(
+        seeds = [ROLE_SEED, operator.key().as_ref()],
+        bump = operator_roles.bump,
+        constraint = operator_roles.has(Role::RegistryManager) @ EscrowError::MissingRole
+    )]
+    pub operator_roles: Account<'info, RoleAssignment>,
+
+    /// Legacy sponsor registry being drained
+    #[account(
+        mut,
+        seeds = [SPONSOR_REGISTRY_SEED],
+        bump = sponsor_registry.bump
+    )]
+    pub sponsor_registry: Account<'info, SponsorRegistry>,
+
+    /// The migrated sponsor's account (created unless it already has a
+    /// ledger or was registered again)
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = 8 + SponsorAccount::INIT_SPACE,
+        seeds = [SPONSOR_SEED, sponsor_pubkey.as_ref()],
+        bump
+    )]
+    pub sponsor_account: Account<'info, SponsorAccount>,
 
     pub system_program: Program<'info, System>,
 }
 
+#[cfg(feature = "legacy-fixtures")]
 #[derive(Accounts)]
-pub struct BatchDisburse<'info> {
-    /// Only the admin can trigger batch disbursements
+pub struct WriteLegacyRegistries<'info> {
+    /// Any admin can write the fixtures (pays for the registries)
     #[account(
         mut,
-        constraint = admin.key() == config.admin @ EscrowError::Unauthorized
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized
     )]
     pub admin: Signer<'info>,
 
-    /// Config to verify admin, update totals, and track batch week
+    /// Config to verify admin
     #[account(
-        mut,
         seeds = [CONFIG_SEED],
         bump = config.bump
     )]
     pub config: Account<'info, Config>,
 
-    /// NGO registry to verify all recipients
+    /// Legacy NGO registry
     #[account(
-        mut,
+        init_if_needed,
+        payer = admin,
+        space = NgoRegistry::SPACE,
         seeds = [NGO_REGISTRY_SEED],
-        bump = ngo_registry.bump
+        bump
     )]
     pub ngo_registry: Account<'info, NgoRegistry>,
 
-    /// The escrow vault sending funds
-    /// CHECK: Validated by seeds
+    /// Legacy sponsor registry
     #[account(
-        mut,
-        seeds = [ESCROW_SEED],
-        bump = config.vault_bump
+        init_if_needed,
+        payer = admin,
+        space = SponsorRegistry::SPACE,
+        seeds = [SPONSOR_REGISTRY_SEED],
+        bump
     )]
-    pub escrow_vault: SystemAccount<'info>,
+    pub sponsor_registry: Account<'info, SponsorRegistry>,
 
     pub system_program: Program<'info, System>,
-    // NGO accounts are passed as remaining_accounts
 }
 
 #[derive(Accounts)]
@@ -942,6 +6512,180 @@ pub struct InitializeEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct ConfigMigratedEvent {
+    pub admin: Pubkey,
+    pub total_deposited: u64,
+    pub total_disbursed: u64,
+    pub last_batch_week: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalCreatedEvent {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub action: ProposalAction,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalApprovedEvent {
+    pub proposal_id: u64,
+    pub approver: Pubkey,
+    /// Approvals from current admins after this one
+    pub approvals: u8,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalQueuedEvent {
+    pub proposal_id: u64,
+    pub action: ProposalAction,
+    /// Whether the timelock delay applies to this action
+    pub timelocked: bool,
+    /// Earliest execution time
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalExecutedEvent {
+    pub proposal_id: u64,
+    pub executor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalCancelledEvent {
+    pub proposal_id: u64,
+    pub cancelled_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminsUpdatedEvent {
+    pub admins: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminHandoverProposedEvent {
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminChangedEvent {
+    /// Id of the `AdminChangeRecord` written for this change
+    pub change_id: u64,
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminHandoverCancelledEvent {
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleGrantedEvent {
+    pub holder: Pubkey,
+    pub role: Role,
+    /// Holder's full role bitmask after the change
+    pub roles: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleRevokedEvent {
+    pub holder: Pubkey,
+    pub role: Role,
+    /// Holder's full role bitmask after the change
+    pub roles: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CategoryCapUpdatedEvent {
+    pub category: NgoCategory,
+    pub old_share_bps: u16,
+    pub new_share_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TimelockUpdatedEvent {
+    pub old_delay: i64,
+    pub new_delay: i64,
+    pub disburse_threshold: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalNoticeUpdatedEvent {
+    pub old_notice: i64,
+    pub new_notice: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConversionRateUpdatedEvent {
+    pub old_rate: u64,
+    pub new_rate: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultBufferUpdatedEvent {
+    pub old_buffer: u64,
+    pub new_buffer: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PledgeLimitsUpdatedEvent {
+    pub min_points: u64,
+    pub max_points: u64,
+    pub roll_over: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PriceFeedUpdatedEvent {
+    pub old_feed: Option<Pubkey>,
+    pub new_feed: Option<Pubkey>,
+    pub max_age: i64,
+    pub max_confidence_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PausedEvent {
+    /// Flags paused by this call
+    pub flags: u8,
+    /// All paused flags after this call
+    pub paused: u8,
+    pub pauser: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UnpausedEvent {
+    /// Flags lifted by this call
+    pub flags: u8,
+    /// Flags still paused after this call
+    pub paused: u8,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct DepositEvent {
     pub sponsor: Pubkey,
@@ -957,22 +6701,294 @@ pub struct SponsorRegisteredEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct SponsorLedgerOpenedEvent {
+    pub sponsor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SponsorMigratedEvent {
+    pub sponsor: Pubkey,
+    /// Lamports the legacy entry had deposited
+    pub total_deposited: u64,
+    pub is_verified: bool,
+    /// Whether the entry was added to an existing account
+    pub merged: bool,
+    /// Entries left in the legacy registry
+    pub remaining: u32,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct SponsorRemovedEvent {
     pub sponsor: Pubkey,
+    pub reason: StatusReason,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SponsorReverifiedEvent {
+    pub sponsor: Pubkey,
+    pub reason: StatusReason,
     pub timestamp: i64,
 }
 
 #[event]
 pub struct NgoAddedEvent {
     pub ngo: Pubkey,
+    /// Id that NGO earmarks name
+    pub ngo_id: u64,
     pub name: String,
     pub timestamp: i64,
 }
 
+#[event]
+pub struct NgoAppliedEvent {
+    pub ngo: Pubkey,
+    pub profile: NgoProfile,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NgoApplicationRejectedEvent {
+    pub ngo: Pubkey,
+    pub reason: StatusReason,
+    pub rejected_by: Pubkey,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct NgoRemovedEvent {
     pub ngo: Pubkey,
+    pub reason: StatusReason,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NgoProfileUpdatedEvent {
+    pub ngo: Pubkey,
+    pub old_profile: NgoProfile,
+    pub new_profile: NgoProfile,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NgoVerificationRenewedEvent {
+    pub ngo: Pubkey,
+    pub previous_verified_until: i64,
+    pub verified_until: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NgoWalletRotatedEvent {
+    /// Stable identity of the NGO across rotations
+    pub original_wallet: Pubkey,
+    pub old_wallet: Pubkey,
+    pub new_wallet: Pubkey,
+    pub rotation_count: u32,
+    /// Cumulative receipts carried over to the new account
+    pub total_received: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NgoReactivatedEvent {
+    pub ngo: Pubkey,
+    pub reason: StatusReason,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NgoArchivedEvent {
+    pub ngo: Pubkey,
+    pub original_wallet: Pubkey,
+    pub name: String,
+    pub total_received: u64,
+    pub disbursement_count: u64,
+    pub added_at: i64,
+    pub status_reason: Option<StatusReason>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SponsorArchivedEvent {
+    pub sponsor: Pubkey,
+    pub name: String,
+    pub total_deposited: u64,
+    pub deposit_count: u32,
+    pub last_deposit: i64,
+    pub status_reason: Option<StatusReason>,
+    /// Withdrawable lamports given up (they stay in the vault for NGOs)
+    pub forfeited: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NgoMigratedEvent {
+    pub ngo: Pubkey,
+    /// Lamports the legacy entry had received
+    pub total_received: u64,
+    pub is_active: bool,
+    /// Whether the entry was added to an existing account
+    pub merged: bool,
+    /// Whether the wallet had been rotated away from or archived
+    pub retired: bool,
+    /// Entries left in the legacy registry
+    pub remaining: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintAddedEvent {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub units_per_1000_points: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenDepositEvent {
+    pub sponsor: Pubkey,
+    pub mint: Pubkey,
+    /// Amount the sponsor sent
+    pub amount: u64,
+    /// Amount credited to the vault (after any transfer fee)
+    pub amount_received: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenDisburseEvent {
+    pub mint: Pubkey,
+    pub ngo: Pubkey,
+    /// Amount the NGO received
+    pub amount: u64,
+    /// Transfer fee paid by the vault on top
+    pub fee: u64,
+    pub memo: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenBatchDisburseEvent {
+    pub mint: Pubkey,
+    pub week_id: u64,
+    pub total_points_pledged: u64,
+    /// Base units that would be sent at full value
+    pub total_amount_requested: u64,
+    /// Base units received by NGOs (may be less if pro-rata applied)
+    pub total_amount_disbursed: u64,
+    /// Transfer fees paid by the vault on top
+    pub total_fees: u64,
+    /// Pro-rata percentage in basis points (10000 = 100%), rounded down
+    pub pro_rata_bps: u16,
+    /// Exact pro-rata ratio: base units the vault sent over base units it
+    /// would have sent at full value, fees included in both
+    pub pro_rata_available: u64,
+    pub pro_rata_requested: u64,
+    /// Base units left by flooring each share, handed out by largest remainder
+    pub dust_allocated: u64,
+    pub num_ngos: u8,
+    pub disbursements: Vec<DisbursementDetail>,
+    pub category_totals: Vec<CategoryTotal>,
+    /// Allocations under the minimum skipped for a later batch
+    pub rolled_over: Vec<BatchAllocation>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EarmarkCreatedEvent {
+    pub earmark_id: u64,
+    pub sponsor: Pubkey,
+    pub restriction: EarmarkRestriction,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EarmarkDrawnEvent {
+    pub earmark_id: u64,
+    pub sponsor: Pubkey,
+    pub ngo: Pubkey,
+    pub week_id: u64,
+    pub amount: u64,
+    /// Lamports left on the earmark after this draw
+    pub remaining: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EarmarkRefundedEvent {
+    pub earmark_id: u64,
+    pub sponsor: Pubkey,
+    pub restriction: EarmarkRestriction,
+    /// Lamports paid out to NGOs before the refund
+    pub drawn: u64,
+    /// Unspent lamports returned to the sponsor
+    pub refunded: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MatchingCampaignCreatedEvent {
+    pub campaign_id: u64,
+    pub sponsor: Pubkey,
+    pub ngos: Vec<Pubkey>,
+    pub match_bps: u16,
+    pub cap: u64,
+    pub starts_at: i64,
+    pub ends_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MatchAppliedEvent {
+    pub campaign_id: u64,
+    pub sponsor: Pubkey,
+    pub ngo: Pubkey,
+    pub week_id: u64,
+    pub amount: u64,
+    /// Lamports left on the campaign after this match
+    pub remaining: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MatchingCampaignClosedEvent {
+    pub campaign_id: u64,
+    pub sponsor: Pubkey,
+    /// Lamports matched over the campaign's lifetime
+    pub matched: u64,
+    /// Unused lamports returned to the sponsor
+    pub refunded: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalRequestedEvent {
+    pub sponsor: Pubkey,
+    pub amount: u64,
+    pub shares: u128,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalExecutedEvent {
+    pub sponsor: Pubkey,
+    pub requested_amount: u64,
+    /// Lamports actually paid (the shares' value at execution)
+    pub amount: u64,
+    pub shares: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalCancelledEvent {
+    pub sponsor: Pubkey,
+    pub amount: u64,
     pub timestamp: i64,
 }
 
@@ -981,6 +6997,8 @@ pub struct DisburseEvent {
     pub ngo: Pubkey,
     pub amount: u64,
     pub memo: String,
+    /// Lamports held back in the vault (rent-exempt minimum plus buffer)
+    pub vault_reserve: u64,
     pub timestamp: i64,
 }
 
@@ -994,12 +7012,32 @@ pub struct BatchDisburseEvent {
     pub total_amount_requested: u64,
     /// Actual lamports sent (may be less if pro-rata applied)
     pub total_amount_disbursed: u64,
-    /// Pro-rata percentage in basis points (10000 = 100%)
+    /// Part of the total drawn from earmarked deposits
+    pub total_from_earmarks: u64,
+    /// Part of the total added by matching campaigns
+    pub total_matched: u64,
+    /// Conversion rate applied to this batch
+    pub lamports_per_1000_points: u64,
+    /// SOL/USD price the rate was derived from, if a feed is set
+    pub price: Option<PriceSnapshot>,
+    /// Pro-rata percentage in basis points (10000 = 100%), rounded down
     pub pro_rata_bps: u16,
+    /// Exact pro-rata ratio applied to the unrestricted part: lamports
+    /// paid over lamports requested (equal when fully funded)
+    pub pro_rata_available: u64,
+    pub pro_rata_requested: u64,
+    /// Lamports left by flooring each share, handed out by largest remainder
+    pub dust_allocated: u64,
+    /// Lamports held back in the vault (rent-exempt minimum plus buffer)
+    pub vault_reserve: u64,
     /// Number of NGOs receiving funds
     pub num_ngos: u8,
     /// Detailed breakdown per NGO
     pub disbursements: Vec<DisbursementDetail>,
+    /// Points and lamports per cause category
+    pub category_totals: Vec<CategoryTotal>,
+    /// Allocations under the minimum skipped for a later batch
+    pub rolled_over: Vec<BatchAllocation>,
     pub timestamp: i64,
 }
 
@@ -1009,7 +7047,7 @@ pub struct BatchDisburseEvent {
 
 #[error_code]
 pub enum EscrowError {
-    #[msg("Only the admin can perform this action")]
+    #[msg("Only an admin can perform this action")]
     Unauthorized,
     #[msg("Amount must be greater than zero")]
     InvalidAmount,
@@ -1043,4 +7081,104 @@ pub enum EscrowError {
     SponsorAlreadyExists,
     #[msg("Sponsor registry is at maximum capacity")]
     SponsorRegistryFull,
+    #[msg("Proposal is not pending")]
+    ProposalNotPending,
+    #[msg("Admin has already approved this proposal")]
+    ProposalAlreadyApproved,
+    #[msg("Proposal does not match the requested action")]
+    ProposalMismatch,
+    #[msg("Proposal has not reached the approval threshold")]
+    ThresholdNotMet,
+    #[msg("Threshold must be between 1 and the number of admins")]
+    InvalidThreshold,
+    #[msg("Admin set exceeds maximum size of 10")]
+    TooManyAdmins,
+    #[msg("Admin set contains a duplicate key")]
+    DuplicateAdmin,
+    #[msg("Key is not in the admin set")]
+    AdminNotFound,
+    #[msg("An admin handover is already pending")]
+    AdminHandoverPending,
+    #[msg("No admin handover is pending")]
+    NoPendingAdmin,
+    #[msg("Signer does not hold the required role")]
+    MissingRole,
+    #[msg("Role is already granted to this key")]
+    RoleAlreadyGranted,
+    #[msg("Timelocked proposal was never queued; create a new proposal")]
+    ProposalNotQueued,
+    #[msg("Timelock delay has not elapsed")]
+    TimelockNotExpired,
+    #[msg("Timelock delay must be at most 30 days, and non-zero at initialize")]
+    InvalidTimelockDelay,
+    #[msg("This instruction is paused by the circuit breaker")]
+    Paused,
+    #[msg("Pause flags must be a non-empty combination of deposits, disbursements and registry")]
+    InvalidPauseFlags,
+    #[msg("NGO is already active")]
+    NgoAlreadyActive,
+    #[msg("Sponsor is not verified")]
+    SponsorNotVerified,
+    #[msg("Sponsor is already verified")]
+    SponsorAlreadyVerified,
+    #[msg("Website exceeds maximum length (128 characters)")]
+    WebsiteTooLong,
+    #[msg("Metadata URI exceeds maximum length (128 characters)")]
+    MetadataUriTooLong,
+    #[msg("Country code must be two uppercase ISO 3166-1 letters")]
+    InvalidCountryCode,
+    #[msg("New wallet must differ from the current wallet")]
+    SameWallet,
+    #[msg("NGO verification has expired and must be renewed")]
+    NgoVerificationExpired,
+    #[msg("Verification expiry must be in the future and at most two years ahead")]
+    InvalidVerificationExpiry,
+    #[msg("Category cap must be at most 10000 basis points")]
+    InvalidCategoryCap,
+    #[msg("A cause category is over its maximum share of this batch")]
+    CategoryCapExceeded,
+    #[msg("Only deactivated NGOs can be archived")]
+    NgoStillActive,
+    #[msg("Only unverified sponsors can be archived")]
+    SponsorStillVerified,
+    #[msg("Match ratio must be greater than zero")]
+    InvalidMatchRatio,
+    #[msg("A matching campaign must name 1 to 10 distinct NGOs")]
+    InvalidCampaignNgos,
+    #[msg("Campaign must start before it ends and end in the future")]
+    InvalidCampaignWindow,
+    #[msg("Matching campaign has not ended yet")]
+    CampaignNotEnded,
+    #[msg("Campaign tag exceeds maximum length (32 characters)")]
+    CampaignTagTooLong,
+    #[msg("Deposit receipts require the sponsor's ledger account")]
+    ReceiptRequiresLedger,
+    #[msg("Withdrawal exceeds the sponsor's withdrawable balance")]
+    InsufficientSponsorBalance,
+    #[msg("Withdrawal notice period has not elapsed")]
+    WithdrawalNoticeNotElapsed,
+    #[msg("Withdrawal notice must be between 0 and 30 days")]
+    InvalidWithdrawalNotice,
+    #[msg("Price feed needs a max age of 1s to 1 hour and a confidence limit of 1 to 10000 bps")]
+    InvalidPriceFeedSettings,
+    #[msg("The configured price feed account must be passed")]
+    PriceFeedRequired,
+    #[msg("Account is not a Pyth price feed")]
+    InvalidPriceFeed,
+    #[msg("Price feed is not currently trading")]
+    PriceUnavailable,
+    #[msg("Price feed is older than the maximum price age")]
+    StalePrice,
+    #[msg("Price feed confidence interval is too wide")]
+    PriceConfidenceTooWide,
+    #[msg("Allocation is below the minimum pledge")]
+    PledgeBelowMinimum,
+    #[msg("Allocation is above the maximum pledge")]
+    PledgeAboveMaximum,
+    #[msg("Maximum pledge must be zero (no maximum) or at least the minimum")]
+    InvalidPledgeLimits,
+    #[msg("A batch lists the same NGO more than once")]
+    DuplicateNgo,
+    #[msg("Sponsor has a pending withdrawal request")]
+    WithdrawalPending,
 }
@@ -24,6 +24,8 @@ const idlPath = path.join(__dirname, "../target/idl/ecoscore_donation.json");
 const idl = JSON.parse(fs.readFileSync(idlPath, "utf8"));
 
 const PROGRAM_ID = new PublicKey("Ff9wbBku1gd8wEoXej6YMxqiyw6eUEGqzCJBNLoHzTqv");
+// Timelock delay (seconds) set at initialize; use days on mainnet
+const TIMELOCK_DELAY = 60;
 
 // Derive PDAs (v2 seeds for fresh deployment)
 const [configPda] = PublicKey.findProgramAddressSync(
@@ -122,7 +124,7 @@ async function main() {
     console.log("━".repeat(70));
     console.log("ESCROW ALREADY INITIALIZED");
     console.log("━".repeat(70));
-    console.log("Admin:", config.admin.toString());
+    console.log("Admins:", config.admins.map((a: PublicKey) => a.toString()).join(", "));
     console.log("Total Deposited:", config.totalDeposited.toNumber() / LAMPORTS_PER_SOL, "SOL");
     console.log("Total Disbursed:", config.totalDisbursed.toNumber() / LAMPORTS_PER_SOL, "SOL");
     console.log("Total Points Redeemed:", config.totalPointsRedeemed.toNumber());
@@ -140,7 +142,7 @@ async function main() {
 
     try {
       const tx = await program.methods
-        .initialize()
+        .initialize(new anchor.BN(TIMELOCK_DELAY))
         .accounts({
           admin: wallet.publicKey,
           config: configPda,
@@ -170,7 +172,7 @@ async function main() {
   const depositAmount = 0.05 * LAMPORTS_PER_SOL; // 0.05 SOL
   try {
     const tx = await program.methods
-      .deposit(new anchor.BN(depositAmount))
+      .deposit(new anchor.BN(depositAmount), null)
       .accounts({
         sponsor: wallet.publicKey,
         config: configPda,
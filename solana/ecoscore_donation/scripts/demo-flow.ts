@@ -52,7 +52,7 @@ async function main() {
   const depositAmount = 2 * LAMPORTS_PER_SOL;
 
   const depositTx = await program.methods
-    .deposit(new anchor.BN(depositAmount))
+    .deposit(new anchor.BN(depositAmount), null)
     .accountsPartial({
       sponsor: patagonia.publicKey,
     })
@@ -15,6 +15,8 @@ const idl = JSON.parse(fs.readFileSync(idlPath, "utf-8"));
 
 // Program ID
 const PROGRAM_ID = new PublicKey("Ff9wbBku1gd8wEoXej6YMxqiyw6eUEGqzCJBNLoHzTqv");
+// Timelock delay (seconds) set at initialize; use days on mainnet
+const TIMELOCK_DELAY = 60;
 
 // Load wallet from default location
 const walletPath = process.env.SOLANA_WALLET_PATH ||
@@ -66,14 +68,6 @@ async function main() {
     [Buffer.from("config_v3")],
     PROGRAM_ID
   );
-  const [ngoRegistryPda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("ngo_registry_v3")],
-    PROGRAM_ID
-  );
-  const [sponsorRegistryPda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("sponsor_registry_v3")],
-    PROGRAM_ID
-  );
   const [escrowVaultPda] = PublicKey.findProgramAddressSync(
     [Buffer.from("escrow_v3")],
     PROGRAM_ID
@@ -81,7 +75,6 @@ async function main() {
 
   console.log("PDAs:");
   console.log(`  Config: ${configPda.toString()}`);
-  console.log(`  NGO Registry: ${ngoRegistryPda.toString()}`);
   console.log(`  Escrow Vault: ${escrowVaultPda.toString()}\n`);
 
   // Check if already initialized
@@ -89,15 +82,15 @@ async function main() {
   try {
     const config = await (program.account as any).config.fetch(configPda);
     console.log("Program already initialized!");
-    console.log(`  Admin: ${config.admin.toString()}`);
+    console.log(`  Admins: ${config.admins.map((a: PublicKey) => a.toString()).join(", ")}`);
     console.log(`  Total Deposited: ${config.totalDeposited.toNumber() / 1e9} SOL`);
     console.log(`  Total Disbursed: ${config.totalDisbursed.toNumber() / 1e9} SOL\n`);
 
     // Show current NGOs
-    const registry = await (program.account as any).ngoRegistry.fetch(ngoRegistryPda);
-    console.log(`Current NGOs registered: ${registry.ngos.length}`);
-    registry.ngos.forEach((ngo: any, i: number) => {
-      console.log(`  ${i + 1}. ${ngo.name} - ${ngo.pubkey.toString()}`);
+    const ngoAccounts = await (program.account as any).ngoAccount.all();
+    console.log(`Current NGOs registered: ${ngoAccounts.length}`);
+    ngoAccounts.forEach(({ account: ngo }: any, i: number) => {
+      console.log(`  ${i + 1}. ${ngo.name} - ${ngo.wallet.toString()}`);
     });
     console.log("\nWill add missing NGOs and sponsors...\n");
     alreadyInitialized = true;
@@ -109,12 +102,10 @@ async function main() {
   if (!alreadyInitialized) {
     try {
       const tx = await (program.methods as any)
-        .initialize()
+        .initialize(new anchor.BN(TIMELOCK_DELAY))
         .accountsPartial({
           admin: walletKeypair.publicKey,
           config: configPda,
-          ngoRegistry: ngoRegistryPda,
-          sponsorRegistry: sponsorRegistryPda,
           escrowVault: escrowVaultPda,
         })
         .signers([walletKeypair])
@@ -161,10 +152,10 @@ async function main() {
         .addNgo(ngoKeypair.publicKey, ngo.name)
         .accountsPartial({
           admin: walletKeypair.publicKey,
+          ngo: ngoKeypair.publicKey,
           config: configPda,
-          ngoRegistry: ngoRegistryPda,
         })
-        .signers([walletKeypair])
+        .signers([walletKeypair, ngoKeypair])
         .rpc();
 
       console.log(`Added ${ngo.name}: ${ngoKeypair.publicKey.toString()}`);
@@ -191,7 +182,6 @@ async function main() {
         .accountsPartial({
           admin: walletKeypair.publicKey,
           config: configPda,
-          sponsorRegistry: sponsorRegistryPda,
         })
         .signers([walletKeypair])
         .rpc();
@@ -213,11 +203,11 @@ async function main() {
   try {
     const depositAmount = 0.5 * 1_000_000_000; // 0.5 SOL in lamports
     const tx = await (program.methods as any)
-      .deposit(new anchor.BN(depositAmount))
+      .deposit(new anchor.BN(depositAmount), null)
       .accountsPartial({
         sponsor: walletKeypair.publicKey,
         config: configPda,
-        sponsorRegistry: sponsorRegistryPda,
+        sponsorAccount: null,
         escrowVault: escrowVaultPda,
       })
       .signers([walletKeypair])
@@ -19,6 +19,9 @@ import * as fs from "fs";
 // CONFIGURATION
 // =============================================================================
 
+// Timelock delay (seconds) set at initialize; use days on mainnet
+const TIMELOCK_DELAY = 60;
+
 const NGOS = [
   "Greenpeace",
   "Environmental Defense Fund (EDF)",
@@ -91,7 +94,7 @@ async function main() {
   if (needsInit) {
     console.log("\nInitializing program...");
     await program.methods
-      .initialize()
+      .initialize(new anchor.BN(TIMELOCK_DELAY))
       .accountsPartial({
         admin: provider.wallet.publicKey,
       })
@@ -119,7 +122,9 @@ async function main() {
         .addNgo(keypair.publicKey, ngoName)
         .accountsPartial({
           admin: provider.wallet.publicKey,
+          ngo: keypair.publicKey,
         })
+        .signers([keypair])
         .rpc();
       console.log(`  [OK] ${ngoName}`);
       console.log(`       ${keypair.publicKey.toString()}`);